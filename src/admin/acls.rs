@@ -0,0 +1,183 @@
+use crate::api_client::{ApiClientTrait, ApiRequestError};
+use crate::kafka_protocol::acl::{AclBinding, AclBindingFilter};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::createacls_request::CreateAclsRequest;
+use crate::kafka_protocol::protocol_requests::deleteacls_request::DeleteAclsRequest;
+use crate::kafka_protocol::protocol_requests::describeacls_request::DescribeAclsRequest;
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::createacls_response::CreateAclsResponse;
+use crate::kafka_protocol::protocol_responses::deleteacls_response::{DeleteAclsResponse, MatchingAcl};
+use crate::kafka_protocol::protocol_responses::describeacls_response::DescribeAclsResponse;
+use crate::KafkaServerAddr;
+
+pub fn describe_acls<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    filter: AclBindingFilter,
+) -> Result<Vec<AclBinding>, ApiRequestError> {
+    let result: Result<Response<DescribeAclsResponse>, ApiRequestError> =
+        api_client.request(bootstrap_server, Request::of(DescribeAclsRequest { filter }));
+    result.and_then(|response| match response.response_message.error_code {
+        0 => Ok(response.response_message.acl_bindings()),
+        error_code => Err(acl_error("DescribeAcls", error_code, response.response_message.error_message)),
+    })
+}
+
+/// Creates ACLs, returning the result of each creation in the same order.
+/// Creations the broker rejected do not prevent the others from being created.
+///
+pub fn create_acls<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    creations: Vec<AclBinding>,
+) -> Result<Vec<Result<(), ApiRequestError>>, ApiRequestError> {
+    let result: Result<Response<CreateAclsResponse>, ApiRequestError> =
+        api_client.request(bootstrap_server, Request::of(CreateAclsRequest { creations }));
+    result.map(|response| {
+        response
+            .response_message
+            .results
+            .into_iter()
+            .map(|result| match result.error_code {
+                0 => Ok(()),
+                error_code => Err(acl_error("CreateAcls", error_code, result.error_message)),
+            })
+            .collect()
+    })
+}
+
+/// The result of a DeleteAcls filter
+#[derive(Debug)]
+pub struct AclDeletion {
+    pub deleted_acls: Vec<AclBinding>,
+    /// the failure of the filter itself, which then matched no ACLs
+    pub error: Option<ApiRequestError>,
+    /// the ACLs which matched but failed to be deleted
+    pub undeleted_acls: Vec<(AclBinding, ApiRequestError)>,
+}
+
+/// Deletes the ACLs matching any of the filters, returning the result of
+/// each filter in the same order. A filter failing, or an ACL it matched
+/// failing to be deleted, does not prevent the other deletions.
+///
+pub fn delete_acls<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    filters: Vec<AclBindingFilter>,
+) -> Result<Vec<AclDeletion>, ApiRequestError> {
+    let result: Result<Response<DeleteAclsResponse>, ApiRequestError> =
+        api_client.request(bootstrap_server, Request::of(DeleteAclsRequest { filters }));
+    result.map(|response| {
+        response
+            .response_message
+            .filter_results
+            .into_iter()
+            .map(|filter_result| {
+                let error = match filter_result.error_code {
+                    0 => None,
+                    error_code => Some(acl_error("DeleteAcls", error_code, filter_result.error_message)),
+                };
+                let (deleted, undeleted): (Vec<MatchingAcl>, Vec<MatchingAcl>) =
+                    filter_result.matching_acls.into_iter().partition(|matching_acl| matching_acl.error_code == 0);
+                AclDeletion {
+                    deleted_acls: deleted.into_iter().map(|matching_acl| matching_acl.acl).collect(),
+                    error,
+                    undeleted_acls: undeleted
+                        .into_iter()
+                        .map(|matching_acl| (matching_acl.acl, acl_error("DeleteAcls", matching_acl.error_code, matching_acl.error_message)))
+                        .collect(),
+                }
+            })
+            .collect()
+    })
+}
+
+fn acl_error(api: &str, error_code: i16, error_message: Option<String>) -> ApiRequestError {
+    ApiRequestError::of(format!("{} failed with error code {}: {}", api, error_code, error_message.unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api_client::tests::MockApiClient;
+    use crate::kafka_protocol::acl::*;
+    use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+
+    use super::*;
+
+    #[test]
+    fn verify_delete_acls() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let response = vec![
+            0, 0, 0, 0, 0, 0, 0, 1, // throttle_time_ms, filter_results
+            0, 0, 255, 255, 0, 0, 0, 1, // error_code, error_message, matching_acls
+            0, 0, 255, 255, 2, 0, 1, 116, 3, 0, 6, 85, 115, 101, 114, 58, 97, 0, 1, 42, 3, 3,
+        ];
+        let api_client = MockApiClient::new().respond(&bootstrap_server, DeleteAclsRequest::api_key(), response);
+
+        let filter = AclBindingFilter { principal: Some(String::from("User:a")), ..AclBindingFilter::any() };
+        let deletions = delete_acls(&api_client, &bootstrap_server, vec![filter]).unwrap();
+        assert!(deletions[0].error.is_none() && deletions[0].undeleted_acls.is_empty());
+        assert_eq!(
+            vec![AclBinding {
+                pattern: ResourcePattern {
+                    resource_type: AclResourceType::Topic,
+                    resource_name: String::from("t"),
+                    pattern_type: AclPatternType::Literal
+                },
+                entry: AccessControlEntry {
+                    principal: String::from("User:a"),
+                    host: String::from("*"),
+                    operation: AclOperation::Read,
+                    permission_type: AclPermissionType::Allow
+                },
+            }],
+            deletions[0].deleted_acls
+        );
+    }
+
+    #[test]
+    fn verify_delete_acls_failures() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let response = vec![
+            0, 0, 0, 0, 0, 0, 0, 3, // throttle_time_ms, filter_results
+            0, 31, 0, 2, 110, 111, 0, 0, 0, 0, // a failed filter
+            0, 0, 255, 255, 0, 0, 0, 2, // a filter matching two ACLs, of which the first is not deleted
+            0, 31, 255, 255, 2, 0, 1, 116, 3, 0, 6, 85, 115, 101, 114, 58, 97, 0, 1, 42, 3, 3, //
+            0, 0, 255, 255, 2, 0, 1, 117, 3, 0, 6, 85, 115, 101, 114, 58, 97, 0, 1, 42, 3, 3, //
+            0, 0, 255, 255, 0, 0, 0, 0, // a filter matching no ACLs
+        ];
+        let api_client = MockApiClient::new().respond(&bootstrap_server, DeleteAclsRequest::api_key(), response);
+
+        let filters = vec![AclBindingFilter::any(), AclBindingFilter::any(), AclBindingFilter::any()];
+        let deletions = delete_acls(&api_client, &bootstrap_server, filters).unwrap();
+        assert!(deletions[0].error.as_ref().unwrap().error.contains("error code 31: no"));
+        assert_eq!(vec!["u"], deletions[1].deleted_acls.iter().map(|acl| acl.pattern.resource_name.as_str()).collect::<Vec<&str>>());
+        assert_eq!("t", deletions[1].undeleted_acls[0].0.pattern.resource_name);
+        assert!(deletions[1].error.is_none() && deletions[2].error.is_none());
+        assert!(deletions[2].deleted_acls.is_empty());
+    }
+
+    #[test]
+    fn verify_create_acls() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let response = vec![0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 255, 255, 0, 31, 0, 2, 110, 111];
+        let api_client = MockApiClient::new().respond(&bootstrap_server, CreateAclsRequest::api_key(), response);
+
+        let acl = AclBinding {
+            pattern: ResourcePattern {
+                resource_type: AclResourceType::Topic,
+                resource_name: String::from("t"),
+                pattern_type: AclPatternType::Literal,
+            },
+            entry: AccessControlEntry {
+                principal: String::from("User:a"),
+                host: String::from("*"),
+                operation: AclOperation::Read,
+                permission_type: AclPermissionType::Allow,
+            },
+        };
+        let results = create_acls(&api_client, &bootstrap_server, vec![acl.clone(), acl]).unwrap();
+        assert!(results[0].is_ok());
+        assert!(results[1].as_ref().unwrap_err().error.contains("error code 31: no"));
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::BTreeMap;
+
+use crate::api_client::{ApiClientTrait, ApiRequestError};
+use crate::kafka_protocol::api_verification::{is_supported, ApiVersionQuery, KafkaApiVersioned};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::alterconfigs_request;
+use crate::kafka_protocol::protocol_requests::alterconfigs_request::AlterConfigsRequest;
+use crate::kafka_protocol::protocol_requests::describeconfigs_request;
+use crate::kafka_protocol::protocol_requests::describeconfigs_request::DescribeConfigsRequest;
+use crate::kafka_protocol::protocol_requests::incrementalalterconfigs_request::{
+    AlterableConfig, ConfigOperation, IncrementalAlterConfigsRequest, Resource,
+};
+use crate::kafka_protocol::protocol_requests::ResourceTypes;
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::alterconfigs_response::AlterConfigsResponse;
+use crate::kafka_protocol::protocol_responses::describeconfigs_response;
+use crate::kafka_protocol::protocol_responses::describeconfigs_response::{ConfigSource, DescribeConfigsResponse};
+use crate::kafka_protocol::protocol_responses::incrementalalterconfigs_response::IncrementalAlterConfigsResponse;
+use crate::KafkaServerAddr;
+
+// values of list configs (e.g. cleanup.policy) are comma separated
+const LIST_SEPARATOR: char = ',';
+
+/// Alters only the given config entries of each resource, through
+/// IncrementalAlterConfigs when the broker advertises support for it, and
+/// otherwise through `alter_configs_by_replacement`. Broker resources must
+/// be sent to that broker.
+///
+pub fn alter_configs<T: ApiClientTrait>(
+    api_client: &T,
+    server_addr: &KafkaServerAddr,
+    resources: Vec<Resource>,
+    validate_only: bool,
+) -> Result<(), ApiRequestError> {
+    let incremental_alter_configs = ApiVersionQuery(IncrementalAlterConfigsRequest::api_key(), IncrementalAlterConfigsRequest::version());
+    if is_supported(api_client, server_addr, &incremental_alter_configs)? {
+        let response: Response<IncrementalAlterConfigsResponse> =
+            api_client.request(server_addr, Request::of(IncrementalAlterConfigsRequest { resources, validate_only }))?;
+        let failures = response
+            .response_message
+            .responses
+            .into_iter()
+            .filter(|response| response.error_code != 0)
+            .map(|response| format!("{} ({}: {})", response.resource_name, response.error_code, response.error_message.unwrap_or_default()))
+            .collect::<Vec<String>>();
+        match failures.as_slice() {
+            [] => Ok(()),
+            failures => Err(ApiRequestError::of(format!("IncrementalAlterConfigs failed for {}", failures.join(", ")))),
+        }
+    } else {
+        alter_configs_by_replacement(api_client, server_addr, resources, validate_only)
+    }
+}
+
+/// AlterConfigs replaces every config a resource has set with those sent, so
+/// the resource's current configs are described first and the changes merged
+/// into them before sending the full set. Only configs set on the resource
+/// itself are kept, rather than defaults or those inherited from the broker.
+///
+/// Sensitive configs are described without their value and so cannot be kept,
+/// which fails the call unless they are being set or deleted.
+///
+pub fn alter_configs_by_replacement<T: ApiClientTrait>(
+    api_client: &T,
+    server_addr: &KafkaServerAddr,
+    resources: Vec<Resource>,
+    validate_only: bool,
+) -> Result<(), ApiRequestError> {
+    let request = Request::of(DescribeConfigsRequest {
+        resources: resources
+            .iter()
+            .map(|resource| describeconfigs_request::Resource {
+                resource_type: resource.resource_type,
+                resource_name: resource.resource_name.clone(),
+                config_names: None,
+            })
+            .collect(),
+        include_synonyms: false,
+    });
+    let response: Response<DescribeConfigsResponse> = api_client.request(server_addr, request)?;
+    let described_resources = response.response_message.resources;
+
+    let resources = resources
+        .into_iter()
+        .map(|resource| {
+            described_resources
+                .iter()
+                .find(|described| described.resource_type == resource.resource_type && described.resource_name == resource.resource_name)
+                .ok_or_else(|| ApiRequestError::of(format!("DescribeConfigs did not describe {}", resource.resource_name)))
+                .and_then(|described| match described.error_code {
+                    0 => merge_configs(described, resource),
+                    error_code => Err(ApiRequestError::of(format!(
+                        "DescribeConfigs failed for {} with error code {}: {}",
+                        described.resource_name,
+                        error_code,
+                        described.error_message.clone().unwrap_or_default()
+                    ))),
+                })
+        })
+        .collect::<Result<Vec<alterconfigs_request::Resource>, ApiRequestError>>()?;
+
+    let response: Response<AlterConfigsResponse> = api_client.request(server_addr, Request::of(AlterConfigsRequest { resources, validate_only }))?;
+    let failures = response
+        .response_message
+        .resources
+        .into_iter()
+        .filter(|resource| resource.error_code != 0)
+        .map(|resource| format!("{} ({}: {})", resource.resource_name, resource.error_code, resource.error_message.unwrap_or_default()))
+        .collect::<Vec<String>>();
+    match failures.as_slice() {
+        [] => Ok(()),
+        failures => Err(ApiRequestError::of(format!("AlterConfigs failed for {}", failures.join(", ")))),
+    }
+}
+
+/// The source of the configs set on a resource itself, where a broker's
+/// dynamic configs are either its own or, for the empty broker name, the
+/// defaults of every broker.
+///
+fn own_config_source(resource: &describeconfigs_response::Resource) -> i8 {
+    if resource.resource_type == ResourceTypes::Topic as i8 {
+        ConfigSource::TopicConfig as i8
+    } else if resource.resource_name.is_empty() {
+        ConfigSource::DynamicDefaultBrokerConfig as i8
+    } else {
+        ConfigSource::DynamicBrokerConfig as i8
+    }
+}
+
+fn merge_configs(described: &describeconfigs_response::Resource, resource: Resource) -> Result<alterconfigs_request::Resource, ApiRequestError> {
+    let config_source = own_config_source(described);
+    let own_entries = described.config_entries.iter().filter(|entry| entry.config_source == config_source);
+
+    let mut configs: BTreeMap<String, Option<String>> = BTreeMap::new();
+    let mut sensitive_configs: Vec<String> = vec![];
+    for entry in own_entries {
+        if entry.is_sensitive && entry.config_value.is_none() {
+            sensitive_configs.push(entry.config_name.clone());
+        }
+        configs.insert(entry.config_name.clone(), entry.config_value.clone());
+    }
+
+    for AlterableConfig { name, config_operation, value } in resource.configs {
+        sensitive_configs.retain(|sensitive_config| *sensitive_config != name);
+        match config_operation {
+            operation if operation == ConfigOperation::Set as i8 => {
+                configs.insert(name, value);
+            }
+            operation if operation == ConfigOperation::Delete as i8 => {
+                configs.remove(&name);
+            }
+            operation if operation == ConfigOperation::Append as i8 || operation == ConfigOperation::Subtract as i8 => {
+                let mut list = configs.get(&name).cloned().flatten().map(|current| split_list(&current)).unwrap_or_default();
+                let values = value.map(|value| split_list(&value)).unwrap_or_default();
+                if operation == ConfigOperation::Append as i8 {
+                    list.extend(values.into_iter().filter(|value| !list.contains(value)).collect::<Vec<String>>());
+                } else {
+                    list.retain(|current| !values.contains(current));
+                }
+                configs.insert(name, Some(list.join(&LIST_SEPARATOR.to_string())));
+            }
+            config_operation => return Err(ApiRequestError::of(format!("Unknown config operation {} for {}", config_operation, name))),
+        }
+    }
+
+    match sensitive_configs.as_slice() {
+        [] => Ok(alterconfigs_request::Resource {
+            resource_type: resource.resource_type,
+            resource_name: resource.resource_name,
+            config_entries: configs
+                .into_iter()
+                .map(|(config_name, config_value)| alterconfigs_request::ConfigEntry { config_name, config_value })
+                .collect(),
+        }),
+        sensitive_configs => Err(ApiRequestError::of(format!(
+            "Cannot alter {} without losing the values of its sensitive configs {}",
+            resource.resource_name,
+            sensitive_configs.join(", ")
+        ))),
+    }
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(LIST_SEPARATOR).map(str::trim).filter(|value| !value.is_empty()).map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api_client::tests::{api_versions_response, MockApiClient};
+    use crate::kafka_protocol::api_verification::ApiVersionsRequest;
+    use crate::kafka_protocol::protocol_serializable::ProtocolSerializable;
+
+    use super::*;
+
+    fn string(value: &str) -> Vec<u8> {
+        [vec![0, value.len() as u8], value.as_bytes().to_vec()].concat()
+    }
+
+    fn config(name: &str, operation: ConfigOperation, value: Option<&str>) -> AlterableConfig {
+        AlterableConfig { name: String::from(name), config_operation: operation as i8, value: value.map(String::from) }
+    }
+
+    fn resources(configs: Vec<AlterableConfig>) -> Vec<Resource> {
+        vec![Resource { resource_type: ResourceTypes::Topic as i8, resource_name: String::from("t"), configs }]
+    }
+
+    fn describe_configs_response(entries: &[(&str, Option<&str>, i8, bool)]) -> Vec<u8> {
+        describe_resource_configs_response(ResourceTypes::Topic as i8, "t", entries)
+    }
+
+    fn describe_resource_configs_response(resource_type: i8, resource_name: &str, entries: &[(&str, Option<&str>, i8, bool)]) -> Vec<u8> {
+        let entries = entries.iter().map(|(name, value, config_source, is_sensitive)| {
+            let value = value.map(string).unwrap_or_else(|| vec![255, 255]);
+            [string(name), value, vec![0, *config_source as u8, *is_sensitive as u8, 0, 0, 0, 0]].concat()
+        });
+        [
+            vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 255, 255, resource_type as u8],
+            string(resource_name),
+            (entries.len() as i32).to_be_bytes().to_vec(),
+            entries.collect::<Vec<Vec<u8>>>().concat(),
+        ]
+        .concat()
+    }
+
+    fn api_client(server_addr: &KafkaServerAddr, describe_configs_response: Vec<u8>) -> MockApiClient {
+        MockApiClient::new()
+            .respond(server_addr, ApiVersionsRequest::api_key(), api_versions_response(AlterConfigsRequest::api_key(), 0))
+            .respond(server_addr, DescribeConfigsRequest::api_key(), describe_configs_response)
+            .respond(server_addr, AlterConfigsRequest::api_key(), [vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 255, 255, 2], string("t")].concat())
+    }
+
+    #[test]
+    fn verify_incremental_alter_configs() {
+        let server_addr = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let api_client = MockApiClient::new()
+            .respond(&server_addr, ApiVersionsRequest::api_key(), api_versions_response(IncrementalAlterConfigsRequest::api_key(), 1))
+            .respond(&server_addr, IncrementalAlterConfigsRequest::api_key(), vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 255, 255, 2, 0, 1, 116]);
+
+        alter_configs(&api_client, &server_addr, resources(vec![config("retention.ms", ConfigOperation::Set, Some("1000"))]), false).unwrap();
+        assert_eq!(1, api_client.requests_for(IncrementalAlterConfigsRequest::api_key()).len());
+        assert!(api_client.requests_for(AlterConfigsRequest::api_key()).is_empty());
+    }
+
+    #[test]
+    fn verify_alter_configs_by_replacement() {
+        let server_addr = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let api_client = api_client(
+            &server_addr,
+            describe_configs_response(&[
+                ("cleanup.policy", Some("compact"), ConfigSource::TopicConfig as i8, false),
+                ("max.message.bytes", Some("100"), ConfigSource::TopicConfig as i8, false),
+                ("retention.ms", Some("5"), ConfigSource::TopicConfig as i8, false),
+                ("segment.bytes", Some("100"), ConfigSource::DefaultConfig as i8, false),
+            ]),
+        );
+
+        let configs = vec![
+            config("cleanup.policy", ConfigOperation::Append, Some("delete")),
+            config("max.message.bytes", ConfigOperation::Delete, None),
+            config("retention.ms", ConfigOperation::Set, Some("1000")),
+        ];
+        alter_configs(&api_client, &server_addr, resources(configs), false).unwrap();
+
+        let expected = AlterConfigsRequest {
+            resources: vec![alterconfigs_request::Resource {
+                resource_type: ResourceTypes::Topic as i8,
+                resource_name: String::from("t"),
+                config_entries: vec![
+                    alterconfigs_request::ConfigEntry {
+                        config_name: String::from("cleanup.policy"),
+                        config_value: Some(String::from("compact,delete")),
+                    },
+                    alterconfigs_request::ConfigEntry { config_name: String::from("retention.ms"), config_value: Some(String::from("1000")) },
+                ],
+            }],
+            validate_only: false,
+        };
+        assert_eq!(
+            vec![expected.into_protocol_bytes().unwrap()],
+            api_client.requests_for(AlterConfigsRequest::api_key()).into_iter().map(|(_, bytes)| bytes).collect::<Vec<Vec<u8>>>()
+        );
+    }
+
+    #[test]
+    fn verify_alter_configs_by_replacement_refuses_to_drop_sensitive_configs() {
+        let server_addr = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let api_client = api_client(&server_addr, describe_configs_response(&[("password", None, ConfigSource::TopicConfig as i8, true)]));
+
+        let error =
+            alter_configs(&api_client, &server_addr, resources(vec![config("retention.ms", ConfigOperation::Set, Some("1000"))]), false).unwrap_err();
+        assert!(error.error.contains("sensitive configs password"));
+        assert!(api_client.requests_for(AlterConfigsRequest::api_key()).is_empty());
+    }
+
+    fn alter_configs_request(api_client: &MockApiClient) -> Vec<u8> {
+        api_client.requests_for(AlterConfigsRequest::api_key()).into_iter().map(|(_, bytes)| bytes).next().unwrap()
+    }
+
+    fn config_entry(config_name: &str, config_value: &str) -> alterconfigs_request::ConfigEntry {
+        alterconfigs_request::ConfigEntry { config_name: String::from(config_name), config_value: Some(String::from(config_value)) }
+    }
+
+    #[test]
+    fn verify_alter_configs_by_replacement_subtracts_from_lists() {
+        let server_addr = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let api_client = api_client(
+            &server_addr,
+            describe_configs_response(&[("leader.replication.throttled.replicas", Some("0:1, 0:2,1:1"), ConfigSource::TopicConfig as i8, false)]),
+        );
+
+        let configs = vec![config("leader.replication.throttled.replicas", ConfigOperation::Subtract, Some("0:2,2:2"))];
+        alter_configs(&api_client, &server_addr, resources(configs), false).unwrap();
+
+        let expected = AlterConfigsRequest {
+            resources: vec![alterconfigs_request::Resource {
+                resource_type: ResourceTypes::Topic as i8,
+                resource_name: String::from("t"),
+                config_entries: vec![config_entry("leader.replication.throttled.replicas", "0:1,1:1")],
+            }],
+            validate_only: false,
+        };
+        assert_eq!(expected.into_protocol_bytes().unwrap(), alter_configs_request(&api_client));
+    }
+
+    #[test]
+    fn verify_alter_configs_by_replacement_for_brokers() {
+        let server_addr = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let described_entries = [
+            ("log.cleaner.threads", Some("2"), ConfigSource::DynamicBrokerConfig as i8, false),
+            ("log.retention.ms", Some("5"), ConfigSource::DynamicDefaultBrokerConfig as i8, false),
+            ("num.io.threads", Some("8"), ConfigSource::StaticBrokerConfig as i8, false),
+        ];
+
+        // a broker's own dynamic configs, or for the empty broker name those of every broker
+        for (broker, kept_entry) in [("1", config_entry("log.cleaner.threads", "2")), ("", config_entry("log.retention.ms", "5"))] {
+            let api_client = api_client(&server_addr, describe_resource_configs_response(ResourceTypes::Broker as i8, broker, &described_entries));
+            let resources = vec![Resource {
+                resource_type: ResourceTypes::Broker as i8,
+                resource_name: String::from(broker),
+                configs: vec![config("message.max.bytes", ConfigOperation::Set, Some("100"))],
+            }];
+            alter_configs(&api_client, &server_addr, resources, false).unwrap();
+
+            let expected = AlterConfigsRequest {
+                resources: vec![alterconfigs_request::Resource {
+                    resource_type: ResourceTypes::Broker as i8,
+                    resource_name: String::from(broker),
+                    config_entries: vec![kept_entry, config_entry("message.max.bytes", "100")],
+                }],
+                validate_only: false,
+            };
+            assert_eq!(expected.into_protocol_bytes().unwrap(), alter_configs_request(&api_client), "broker '{}'", broker);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::admin::coordinator::find_group_coordinator;
+use crate::api_client::{ApiClientTrait, ApiRequestError};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::offsetcommit_request::{OffsetCommitRequest, Topic};
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::offsetcommit_response::OffsetCommitResponse;
+use crate::KafkaServerAddr;
+
+/// Commits offsets on behalf of a group, without being a member of it, through
+/// the group's coordinator. The coordinator rejects such commits while the group
+/// has active members, so any partition failing to commit fails the whole call.
+///
+pub fn commit_offsets<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    group_id: &str,
+    topics: Vec<Topic>,
+) -> Result<(), ApiRequestError> {
+    find_group_coordinator(api_client, bootstrap_server, group_id).and_then(|coordinator| {
+        let request = Request::of(OffsetCommitRequest::admin(String::from(group_id), topics));
+        let result: Result<Response<OffsetCommitResponse>, ApiRequestError> = api_client.request(&coordinator, request);
+        result.and_then(|response| {
+            let failures = response
+                .response_message
+                .responses
+                .iter()
+                .flat_map(|response| {
+                    response.partition_responses.iter().filter(|partition_response| partition_response.error_code != 0).map(
+                        move |partition_response| format!("{}-{} ({})", response.topic, partition_response.partition, partition_response.error_code),
+                    )
+                })
+                .collect::<Vec<String>>();
+            match failures.as_slice() {
+                [] => Ok(()),
+                failures => Err(ApiRequestError::of(format!("OffsetCommit failed for group {} on {}", group_id, failures.join(", ")))),
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::admin::coordinator::tests::coordinator_response;
+    use crate::api_client::tests::MockApiClient;
+    use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+    use crate::kafka_protocol::protocol_requests::findcoordinator_request::FindCoordinatorRequest;
+    use crate::kafka_protocol::protocol_requests::offsetcommit_request::Partition;
+
+    use super::*;
+
+    fn topics() -> Vec<Topic> {
+        vec![Topic { topic: String::from("t"), partitions: vec![Partition { partition: 0, committed_offset: 42, committed_metadata: None }] }]
+    }
+
+    fn commit_response(error_code: i16) -> Vec<u8> {
+        vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 116, 0, 0, 0, 1, 0, 0, 0, 0, 0, error_code as u8]
+    }
+
+    #[test]
+    fn verify_commit_offsets() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let coordinator = bootstrap_server.with_host("broker-1", 9092);
+        let api_client = MockApiClient::new()
+            .respond(&bootstrap_server, FindCoordinatorRequest::api_key(), coordinator_response(0, "broker-1"))
+            .respond(&coordinator, OffsetCommitRequest::api_key(), commit_response(0));
+
+        assert!(commit_offsets(&api_client, &bootstrap_server, "g", topics()).is_ok());
+        let requests = api_client.requests_for(OffsetCommitRequest::api_key());
+        assert_eq!(1, requests.len());
+        assert_eq!("broker-1:9092", requests[0].0);
+    }
+
+    #[test]
+    fn verify_commit_offsets_partition_failure() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let coordinator = bootstrap_server.with_host("broker-1", 9092);
+        let api_client = MockApiClient::new()
+            .respond(&bootstrap_server, FindCoordinatorRequest::api_key(), coordinator_response(0, "broker-1"))
+            .respond(&coordinator, OffsetCommitRequest::api_key(), commit_response(25));
+
+        let error = commit_offsets(&api_client, &bootstrap_server, "g", topics()).unwrap_err();
+        assert!(format!("{}", error).contains("t-0 (25)"));
+    }
+}
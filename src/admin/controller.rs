@@ -0,0 +1,18 @@
+use crate::api_client::ApiRequestError;
+use crate::kafka_protocol::protocol_responses::metadata_response::BrokerMetadata;
+use crate::KafkaServerAddr;
+
+/// The controller, as identified by a MetadataResponse's controller_id,
+/// for requests (e.g. partition reassignments) it must handle itself.
+///
+pub(crate) fn controller_server(
+    bootstrap_server: &KafkaServerAddr,
+    brokers: &[BrokerMetadata],
+    controller_id: i32,
+) -> Result<KafkaServerAddr, ApiRequestError> {
+    brokers
+        .iter()
+        .find(|broker| broker.node_id == controller_id)
+        .map(|broker| bootstrap_server.with_host(&broker.host, broker.port))
+        .ok_or_else(|| ApiRequestError::of(format!("Controller {} is not one of the brokers", controller_id)))
+}
@@ -0,0 +1,59 @@
+use crate::api_client::{ApiClientTrait, ApiRequestError};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::findcoordinator_request::{CoordinatorType, FindCoordinatorRequest};
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::findcoordinator_response::FindCoordinatorResponse;
+use crate::KafkaServerAddr;
+
+/// Group requests such as OffsetCommit must be sent to the broker coordinating
+/// the group, which any broker can tell us about.
+///
+pub fn find_group_coordinator<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    group_id: &str,
+) -> Result<KafkaServerAddr, ApiRequestError> {
+    let request = Request::of(FindCoordinatorRequest { coordinator_key: String::from(group_id), coordinator_type: CoordinatorType::Group as i8 });
+    let result: Result<Response<FindCoordinatorResponse>, ApiRequestError> = api_client.request(bootstrap_server, request);
+    result.and_then(|response| match response.response_message.error_code {
+        0 => {
+            let coordinator = response.response_message.coordinator;
+            Ok(bootstrap_server.with_host(&coordinator.host, coordinator.port))
+        }
+        error_code => Err(ApiRequestError::of(format!(
+            "FindCoordinator failed for group {} with error code {}: {}",
+            group_id,
+            error_code,
+            response.response_message.error_message.unwrap_or_default()
+        ))),
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::api_client::tests::MockApiClient;
+    use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+
+    use super::*;
+
+    pub(crate) fn coordinator_response(error_code: i16, host: &str) -> Vec<u8> {
+        [vec![0, 0, 0, 0, 0, error_code as u8, 255, 255, 0, 0, 0, 1, 0, host.len() as u8], host.as_bytes().to_vec(), vec![0, 0, 35, 132]].concat()
+    }
+
+    #[test]
+    fn verify_find_group_coordinator() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let api_client = MockApiClient::new().respond(&bootstrap_server, FindCoordinatorRequest::api_key(), coordinator_response(0, "broker-1"));
+
+        let coordinator = find_group_coordinator(&api_client, &bootstrap_server, "group").unwrap();
+        assert_eq!("broker-1:9092", coordinator.as_socket_addr());
+    }
+
+    #[test]
+    fn verify_find_group_coordinator_not_available() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let api_client = MockApiClient::new().respond(&bootstrap_server, FindCoordinatorRequest::api_key(), coordinator_response(15, ""));
+
+        assert!(find_group_coordinator(&api_client, &bootstrap_server, "group").is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use crate::admin::coordinator::find_group_coordinator;
+use crate::api_client::{ApiClientTrait, ApiRequestError};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::deletegroups_request::DeleteGroupsRequest;
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::deletegroups_response::DeleteGroupsResponse;
+use crate::KafkaServerAddr;
+
+/// The result of deleting a group: the error code of its coordinator, or the
+/// error which prevented its coordinator from being found or asked
+#[derive(Debug)]
+pub struct GroupDeletion {
+    pub group_id: String,
+    pub result: Result<i16, ApiRequestError>,
+}
+
+/// Deletes groups through their coordinators, sending each coordinator one
+/// request for all of the groups it coordinates. Groups that could not be
+/// deleted (e.g. as they still have members, or their coordinator is
+/// unreachable) are reported in their result rather than failing the call,
+/// with the results sorted by group_id.
+///
+pub fn delete_groups<T: ApiClientTrait>(api_client: &T, bootstrap_server: &KafkaServerAddr, group_ids: &[String]) -> Vec<GroupDeletion> {
+    let mut results = vec![];
+    let mut groups_by_coordinator: BTreeMap<String, (KafkaServerAddr, Vec<String>)> = BTreeMap::new();
+    for group_id in group_ids {
+        match find_group_coordinator(api_client, bootstrap_server, group_id) {
+            Ok(coordinator) => groups_by_coordinator.entry(coordinator.as_socket_addr()).or_insert((coordinator, vec![])).1.push(group_id.clone()),
+            Err(error) => results.push(GroupDeletion { group_id: group_id.clone(), result: Err(error) }),
+        }
+    }
+
+    for (coordinator, groups_names) in groups_by_coordinator.into_values() {
+        let response: Result<Response<DeleteGroupsResponse>, ApiRequestError> =
+            api_client.request(&coordinator, Request::of(DeleteGroupsRequest { groups_names: groups_names.clone() }));
+        match response {
+            Ok(response) => results.extend(
+                response
+                    .response_message
+                    .results
+                    .into_iter()
+                    .map(|result| GroupDeletion { group_id: result.group_id, result: Ok(result.error_code) }),
+            ),
+            Err(error) => results.extend(groups_names.into_iter().map(|group_id| GroupDeletion { group_id, result: Err(error.clone()) })),
+        }
+    }
+    results.sort_by(|a, b| a.group_id.cmp(&b.group_id));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::admin::coordinator::tests::coordinator_response;
+    use crate::api_client::tests::MockApiClient;
+    use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+    use crate::kafka_protocol::protocol_requests::findcoordinator_request::FindCoordinatorRequest;
+
+    use super::*;
+
+    #[test]
+    fn verify_delete_groups() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let api_client = MockApiClient::new()
+            .respond(&bootstrap_server, FindCoordinatorRequest::api_key(), coordinator_response(0, "broker-2"))
+            .respond(&bootstrap_server, FindCoordinatorRequest::api_key(), coordinator_response(0, "broker-1"))
+            .respond(&bootstrap_server, FindCoordinatorRequest::api_key(), coordinator_response(0, "broker-2"))
+            .respond(&bootstrap_server.with_host("broker-1", 9092), DeleteGroupsRequest::api_key(), vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 98, 0, 0])
+            .respond(
+                &bootstrap_server.with_host("broker-2", 9092),
+                DeleteGroupsRequest::api_key(),
+                vec![0, 0, 0, 0, 0, 0, 0, 2, 0, 1, 99, 0, 0, 0, 1, 97, 0, 68],
+            );
+
+        let group_ids = vec![String::from("c"), String::from("b"), String::from("a")];
+        let results = delete_groups(&api_client, &bootstrap_server, &group_ids);
+        assert_eq!(vec![("a", 68), ("b", 0), ("c", 0)], error_codes(&results));
+
+        let requests = api_client.requests_for(DeleteGroupsRequest::api_key());
+        assert_eq!(2, requests.len());
+        assert_eq!(("broker-2:9092", vec![0, 0, 0, 2, 0, 1, 99, 0, 1, 97]), (requests[1].0.as_str(), requests[1].1.clone()));
+    }
+
+    #[test]
+    fn verify_delete_groups_with_failing_coordinators() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        // no coordinator is found for c, and broker-2 does not respond to DeleteGroups
+        let api_client = MockApiClient::new()
+            .respond(&bootstrap_server, FindCoordinatorRequest::api_key(), coordinator_response(0, "broker-2"))
+            .respond(&bootstrap_server, FindCoordinatorRequest::api_key(), coordinator_response(0, "broker-1"))
+            .respond(&bootstrap_server.with_host("broker-1", 9092), DeleteGroupsRequest::api_key(), vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 98, 0, 0]);
+
+        let group_ids = vec![String::from("a"), String::from("b"), String::from("c")];
+        let results = delete_groups(&api_client, &bootstrap_server, &group_ids);
+        assert_eq!(vec![("b", 0)], error_codes(&results));
+        let failed: Vec<&str> = results.iter().filter(|deletion| deletion.result.is_err()).map(|deletion| deletion.group_id.as_str()).collect();
+        assert_eq!(vec!["a", "c"], failed);
+    }
+
+    // (group_id, error_code) of the groups whose coordinator answered
+    fn error_codes(results: &[GroupDeletion]) -> Vec<(&str, i16)> {
+        results.iter().filter_map(|deletion| deletion.result.as_ref().ok().map(|error_code| (deletion.group_id.as_str(), *error_code))).collect()
+    }
+}
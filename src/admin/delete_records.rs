@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use crate::admin::partition_leaders::{leader_server, list_offsets, partitions_by_leader};
+use crate::api_client::{ApiClientTrait, ApiRequestError};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::deleterecords_request;
+use crate::kafka_protocol::protocol_requests::deleterecords_request::{DeleteRecordsRequest, HIGH_WATERMARK};
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::deleterecords_response::DeleteRecordsResponse;
+use crate::kafka_protocol::protocol_responses::metadata_response::{BrokerMetadata, TopicMetadata};
+use crate::KafkaServerAddr;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeleteRecordsBefore {
+    Offset(i64),
+    /// milliseconds since the epoch, deleting every record when all are older
+    Timestamp(i64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeletedRecords {
+    pub topic: String,
+    pub partition: i32,
+    /// the partition's new log start offset
+    pub low_watermark: i64,
+    pub error_code: i16,
+}
+
+/// Deletes the records before an offset or timestamp from every partition of
+/// the given topics, through each partition's leader. Partitions failing to be
+/// truncated are reported by their error code rather than failing the call.
+///
+pub fn delete_records<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    brokers: &[BrokerMetadata],
+    topics: &[TopicMetadata],
+    before: &DeleteRecordsBefore,
+    timeout: i32,
+) -> Result<Vec<DeletedRecords>, ApiRequestError> {
+    let timestamp_offsets = match before {
+        DeleteRecordsBefore::Timestamp(timestamp) => list_offsets(api_client, bootstrap_server, brokers, topics, *timestamp)?,
+        DeleteRecordsBefore::Offset(_) => HashMap::new(),
+    };
+
+    let mut deleted_records = vec![];
+    for (leader, partitions_by_topic) in partitions_by_leader(topics, |partition| partition.partition) {
+        let leader_server = leader_server(bootstrap_server, brokers, leader, &partitions_by_topic)?;
+        let topics = partitions_by_topic
+            .into_iter()
+            .map(|(topic, partitions)| {
+                let partitions = partitions
+                    .into_iter()
+                    .map(|partition| {
+                        let offset = match before {
+                            DeleteRecordsBefore::Offset(offset) => *offset,
+                            DeleteRecordsBefore::Timestamp(_) => {
+                                timestamp_offsets.get(&(topic.clone(), partition)).cloned().unwrap_or(HIGH_WATERMARK)
+                            }
+                        };
+                        deleterecords_request::Partition { partition, offset }
+                    })
+                    .collect();
+                deleterecords_request::Topic { topic, partitions }
+            })
+            .collect();
+
+        let response: Response<DeleteRecordsResponse> = api_client.request(&leader_server, Request::of(DeleteRecordsRequest { topics, timeout }))?;
+        for topic in response.response_message.topics {
+            for partition in topic.partitions {
+                deleted_records.push(DeletedRecords {
+                    topic: topic.topic.clone(),
+                    partition: partition.partition,
+                    low_watermark: partition.low_watermark,
+                    error_code: partition.error_code,
+                });
+            }
+        }
+    }
+    deleted_records.sort_by(|a, b| (&a.topic, a.partition).cmp(&(&b.topic, b.partition)));
+    Ok(deleted_records)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api_client::tests::MockApiClient;
+    use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+    use crate::kafka_protocol::protocol_requests::listoffsets_request::ListOffsetsRequest;
+    use crate::kafka_protocol::protocol_responses::metadata_response::PartitionMetadata;
+
+    use super::*;
+
+    fn topics() -> Vec<TopicMetadata> {
+        let partition = |partition, leader| PartitionMetadata {
+            error_code: 0,
+            partition,
+            leader,
+            replicas: vec![leader],
+            isr: vec![leader],
+            offline_replicas: vec![],
+        };
+        vec![TopicMetadata {
+            error_code: 0,
+            topic: String::from("t"),
+            is_internal: false,
+            partition_metadata: vec![partition(0, 1), partition(1, 2)],
+        }]
+    }
+
+    fn brokers() -> Vec<BrokerMetadata> {
+        (1..=2).map(|node_id| BrokerMetadata { node_id, host: format!("broker-{}", node_id), port: 9092, rack: None }).collect()
+    }
+
+    fn delete_records_response(partition: u8, low_watermark: u8, error_code: u8) -> Vec<u8> {
+        vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 116, 0, 0, 0, 1, 0, 0, 0, partition, 0, 0, 0, 0, 0, 0, 0, low_watermark, 0, error_code]
+    }
+
+    #[test]
+    fn verify_delete_records_before_timestamp() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let broker_1 = bootstrap_server.with_host("broker-1", 9092);
+        let broker_2 = bootstrap_server.with_host("broker-2", 9092);
+        // partition 1 has no records at or after the timestamp
+        let list_offsets_response = |partition: u8, offset: i64| {
+            [
+                vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 116, 0, 0, 0, 1, 0, 0, 0, partition, 0, 0],
+                (-1i64).to_be_bytes().to_vec(),
+                offset.to_be_bytes().to_vec(),
+            ]
+            .concat()
+        };
+        let api_client = MockApiClient::new()
+            .respond(&broker_1, ListOffsetsRequest::api_key(), list_offsets_response(0, 7))
+            .respond(&broker_2, ListOffsetsRequest::api_key(), list_offsets_response(1, -1))
+            .respond(&broker_1, DeleteRecordsRequest::api_key(), delete_records_response(0, 7, 0))
+            .respond(&broker_2, DeleteRecordsRequest::api_key(), delete_records_response(1, 30, 0));
+
+        let deleted_records =
+            delete_records(&api_client, &bootstrap_server, &brokers(), &topics(), &DeleteRecordsBefore::Timestamp(1_500_000_000_000), 1000).unwrap();
+        assert_eq!(
+            vec![
+                DeletedRecords { topic: String::from("t"), partition: 0, low_watermark: 7, error_code: 0 },
+                DeletedRecords { topic: String::from("t"), partition: 1, low_watermark: 30, error_code: 0 },
+            ],
+            deleted_records
+        );
+
+        let requests = api_client.requests_for(DeleteRecordsRequest::api_key());
+        assert_eq!(("broker-1:9092", 7), (requests[0].0.as_str(), requests[0].1[22]));
+        assert_eq!(("broker-2:9092", vec![255; 8]), (requests[1].0.as_str(), requests[1].1[15..23].to_vec()));
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+
+use crate::admin::list_groups::BrokerFailure;
+use crate::api_client::{ApiClientTrait, ApiRequestError};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::describelogdirs_request::{DescribeLogDirsRequest, Topic};
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::describelogdirs_response::DescribeLogDirsResponse;
+use crate::kafka_protocol::protocol_responses::metadata_response::BrokerMetadata;
+use crate::KafkaServerAddr;
+
+/// A replica of a partition, as stored in one of a broker's log directories
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplicaLogDir {
+    pub broker_id: i32,
+    pub log_dir: String,
+    pub topic: String,
+    pub partition: i32,
+    pub size: i64,
+    pub offset_lag: i64,
+    pub is_future: bool,
+}
+
+/// A log directory a broker could not describe, e.g. as it is offline
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogDirError {
+    pub broker_id: i32,
+    pub log_dir: String,
+    pub error_code: i16,
+}
+
+/// The log directories of every broker which described them, along with the
+/// brokers which failed to, whose replicas are missing from `replicas`
+#[derive(Clone, Debug)]
+pub struct LogDirsReport {
+    pub replicas: Vec<ReplicaLogDir>,
+    pub errors: Vec<LogDirError>,
+    pub failures: Vec<BrokerFailure>,
+}
+
+impl LogDirsReport {
+    /// Bytes used by each topic, summed over all of its replicas
+    pub fn topic_sizes(&self) -> BTreeMap<String, i64> {
+        let mut sizes = BTreeMap::new();
+        for replica in &self.replicas {
+            *sizes.entry(replica.topic.clone()).or_insert(0) += replica.size;
+        }
+        sizes
+    }
+
+    /// Bytes used on each broker, summed over all of its log directories
+    pub fn broker_sizes(&self) -> BTreeMap<i32, i64> {
+        let mut sizes = BTreeMap::new();
+        for replica in &self.replicas {
+            *sizes.entry(replica.broker_id).or_insert(0) += replica.size;
+        }
+        sizes
+    }
+}
+
+/// Each broker only describes its own log directories, so every broker is
+/// queried and the results merged into one report. A broker failing to
+/// describe its log directories does not discard those of the others.
+///
+pub fn describe_log_dirs<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    brokers: &[BrokerMetadata],
+    topics: Option<Vec<Topic>>,
+) -> LogDirsReport {
+    let mut report = LogDirsReport { replicas: vec![], errors: vec![], failures: vec![] };
+    for broker in brokers {
+        let request = Request::of(DescribeLogDirsRequest { topics: topics.clone() });
+        let result: Result<Response<DescribeLogDirsResponse>, ApiRequestError> =
+            api_client.request(&bootstrap_server.with_host(&broker.host, broker.port), request);
+        let response = match result {
+            Ok(response) => response,
+            Err(error) => {
+                report.failures.push(BrokerFailure { broker_id: broker.node_id, error });
+                continue;
+            }
+        };
+        for log_dir in response.response_message.results {
+            if log_dir.error_code != 0 {
+                report.errors.push(LogDirError { broker_id: broker.node_id, log_dir: log_dir.log_dir, error_code: log_dir.error_code });
+                continue;
+            }
+            for topic in log_dir.topics {
+                for partition in topic.partitions {
+                    report.replicas.push(ReplicaLogDir {
+                        broker_id: broker.node_id,
+                        log_dir: log_dir.log_dir.clone(),
+                        topic: topic.topic.clone(),
+                        partition: partition.partition,
+                        size: partition.partition_size,
+                        offset_lag: partition.offset_lag,
+                        is_future: partition.is_future_key,
+                    });
+                }
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api_client::tests::MockApiClient;
+    use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+
+    use super::*;
+
+    // a log dir "/d" with partition 0 of topic t, and an offline log dir "/e"
+    fn response(size: u8, is_future: u8) -> Vec<u8> {
+        vec![
+            0, 0, 0, 0, 0, 0, 0, 2, // throttle_time_ms, results
+            0, 0, 0, 2, 47, 100, 0, 0, 0, 1, 0, 1, 116, 0, 0, 0, 1, // log dir /d, topic t
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, size, 0, 0, 0, 0, 0, 0, 0, 0, is_future, // partition 0
+            0, 56, 0, 2, 47, 101, 0, 0, 0, 0, // log dir /e
+        ]
+    }
+
+    #[test]
+    fn verify_describe_log_dirs() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let brokers: Vec<BrokerMetadata> =
+            (1..=2).map(|node_id| BrokerMetadata { node_id, host: format!("broker-{}", node_id), port: 9092, rack: None }).collect();
+        let api_client = MockApiClient::new()
+            .respond(&bootstrap_server.with_host("broker-1", 9092), DescribeLogDirsRequest::api_key(), response(100, 0))
+            .respond(&bootstrap_server.with_host("broker-2", 9092), DescribeLogDirsRequest::api_key(), response(60, 1));
+
+        let report = describe_log_dirs(&api_client, &bootstrap_server, &brokers, None);
+        assert_eq!(vec![(String::from("t"), 160)], report.topic_sizes().into_iter().collect::<Vec<(String, i64)>>());
+        assert_eq!(vec![(1, 100), (2, 60)], report.broker_sizes().into_iter().collect::<Vec<(i32, i64)>>());
+        assert!(report.replicas[1].is_future);
+        assert_eq!(LogDirError { broker_id: 2, log_dir: String::from("/e"), error_code: 56 }, report.errors[1]);
+        assert!(report.failures.is_empty());
+    }
+
+    #[test]
+    fn verify_describe_log_dirs_with_failing_broker() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let brokers: Vec<BrokerMetadata> =
+            (1..=2).map(|node_id| BrokerMetadata { node_id, host: format!("broker-{}", node_id), port: 9092, rack: None }).collect();
+        let api_client =
+            MockApiClient::new().respond(&bootstrap_server.with_host("broker-2", 9092), DescribeLogDirsRequest::api_key(), response(60, 0));
+
+        let report = describe_log_dirs(&api_client, &bootstrap_server, &brokers, None);
+        assert_eq!(vec![(2, 60)], report.broker_sizes().into_iter().collect::<Vec<(i32, i64)>>());
+        assert_eq!(vec![1], report.failures.iter().map(|failure| failure.broker_id).collect::<Vec<i32>>());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::admin::controller::controller_server;
+use crate::api_client::{ApiClientTrait, ApiRequestError};
+use crate::kafka_protocol::api_verification::{is_supported, ApiVersionQuery, KafkaApiVersioned};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::electleaders_request::{ElectLeadersRequest, TopicPartitions};
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::electleaders_response::ElectLeadersResponse;
+use crate::kafka_protocol::protocol_responses::metadata_response::{BrokerMetadata, TopicMetadata};
+use crate::KafkaServerAddr;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElectionResult {
+    pub topic: String,
+    pub partition: i32,
+    /// e.g. ELECTION_NOT_NEEDED (84) when the partition is already led by the elected replica
+    pub error_code: i16,
+    pub error_message: Option<String>,
+}
+
+/// The partitions not led by their preferred (i.e. first) replica
+pub fn partitions_without_preferred_leader(topics: &[TopicMetadata]) -> Vec<TopicPartitions> {
+    topics
+        .iter()
+        .map(|topic| TopicPartitions {
+            topic: topic.topic.clone(),
+            partitions: topic
+                .partition_metadata
+                .iter()
+                .filter(|partition| partition.replicas.first() != Some(&partition.leader))
+                .map(|partition| partition.partition)
+                .collect(),
+        })
+        .filter(|topic| !topic.partitions.is_empty())
+        .collect()
+}
+
+/// Elects leaders through the controller, for the given partitions or every
+/// partition when None, with the result of each partition's election.
+/// Requires a controller of Kafka 2.4 or later.
+///
+pub fn elect_leaders<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    brokers: &[BrokerMetadata],
+    controller_id: i32,
+    election_type: i8,
+    topic_partitions: Option<Vec<TopicPartitions>>,
+    timeout: i32,
+) -> Result<Vec<ElectionResult>, ApiRequestError> {
+    let controller = controller_server(bootstrap_server, brokers, controller_id)?;
+    let elect_leaders_v1 = ApiVersionQuery(ElectLeadersRequest::api_key(), ElectLeadersRequest::version());
+    if !is_supported(api_client, &controller, &elect_leaders_v1)? {
+        return Err(ApiRequestError::from("ElectLeaders is not supported by the controller, which requires Kafka 2.4 or later"));
+    }
+    let result: Result<Response<ElectLeadersResponse>, ApiRequestError> =
+        api_client.request(&controller, Request::of(ElectLeadersRequest { election_type, topic_partitions, timeout }));
+    result.and_then(|response| match response.response_message.error_code {
+        0 => Ok(response
+            .response_message
+            .replica_election_results
+            .into_iter()
+            .flat_map(|result| {
+                let topic = result.topic;
+                result.partition_results.into_iter().map(move |partition_result| ElectionResult {
+                    topic: topic.clone(),
+                    partition: partition_result.partition,
+                    error_code: partition_result.error_code,
+                    error_message: partition_result.error_message,
+                })
+            })
+            .collect()),
+        error_code => Err(ApiRequestError::of(format!("ElectLeaders failed with error code {}", error_code))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api_client::tests::{api_versions_response, MockApiClient};
+    use crate::kafka_protocol::api_verification::ApiVersionsRequest;
+    use crate::kafka_protocol::protocol_requests::electleaders_request::ElectionType;
+    use crate::kafka_protocol::protocol_responses::metadata_response::PartitionMetadata;
+
+    use super::*;
+
+    #[test]
+    fn verify_partitions_without_preferred_leader() {
+        let partition = |partition, leader| PartitionMetadata {
+            error_code: 0,
+            partition,
+            leader,
+            replicas: vec![1, 2],
+            isr: vec![1, 2],
+            offline_replicas: vec![],
+        };
+        let topics = vec![
+            TopicMetadata { error_code: 0, topic: String::from("a"), is_internal: false, partition_metadata: vec![partition(0, 1), partition(1, 2)] },
+            TopicMetadata { error_code: 0, topic: String::from("b"), is_internal: false, partition_metadata: vec![partition(0, 1)] },
+        ];
+        assert_eq!(vec![TopicPartitions { topic: String::from("a"), partitions: vec![1] }], partitions_without_preferred_leader(&topics));
+    }
+
+    #[test]
+    fn verify_elect_leaders() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let brokers = vec![BrokerMetadata { node_id: 1, host: String::from("broker-1"), port: 9092, rack: None }];
+        let response = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 97, 0, 0, 0, 1, 0, 0, 0, 1, 0, 84, 255, 255];
+        let controller = bootstrap_server.with_host("broker-1", 9092);
+        let api_client = MockApiClient::new()
+            .respond(&controller, ApiVersionsRequest::api_key(), api_versions_response(ElectLeadersRequest::api_key(), 2))
+            .respond(&controller, ElectLeadersRequest::api_key(), response);
+
+        let topic_partitions = vec![TopicPartitions { topic: String::from("a"), partitions: vec![1] }];
+        let results =
+            elect_leaders(&api_client, &bootstrap_server, &brokers, 1, ElectionType::Preferred as i8, Some(topic_partitions), 1000).unwrap();
+        assert_eq!(vec![ElectionResult { topic: String::from("a"), partition: 1, error_code: 84, error_message: None }], results);
+    }
+
+    #[test]
+    fn verify_elect_leaders_before_kafka_2_4() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let brokers = vec![BrokerMetadata { node_id: 1, host: String::from("broker-1"), port: 9092, rack: None }];
+        let controller = bootstrap_server.with_host("broker-1", 9092);
+        let api_client =
+            MockApiClient::new().respond(&controller, ApiVersionsRequest::api_key(), api_versions_response(ElectLeadersRequest::api_key(), 0));
+
+        let error = elect_leaders(&api_client, &bootstrap_server, &brokers, 1, ElectionType::Preferred as i8, None, 1000).unwrap_err();
+        assert!(error.error.contains("requires Kafka 2.4 or later"));
+        assert!(api_client.requests_for(ElectLeadersRequest::api_key()).is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use crate::api_client::{ApiClientTrait, ApiRequestError};
+use crate::kafka_protocol::api_verification::{is_supported, ApiVersionQuery, KafkaApiVersioned};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::listgroups_request::{LegacyListGroupsRequest, ListGroupsRequest};
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::listgroups_response::{Group, LegacyListGroupsResponse, ListGroupsResponse};
+use crate::kafka_protocol::protocol_responses::metadata_response::BrokerMetadata;
+use crate::KafkaServerAddr;
+
+/// A broker which failed to answer a request (e.g. as it is unreachable),
+/// whose part of a report sent to every broker is missing
+#[derive(Clone, Debug)]
+pub struct BrokerFailure {
+    pub broker_id: i32,
+    pub error: ApiRequestError,
+}
+
+/// The groups of every broker which listed them, along with the brokers
+/// which failed to, whose groups are missing from `groups`
+#[derive(Debug)]
+pub struct GroupsReport {
+    pub groups: Vec<Group>,
+    pub failures: Vec<BrokerFailure>,
+}
+
+/// Each broker only lists the groups it coordinates, so every broker is
+/// queried and the results merged into one list, sorted by group_id. A broker
+/// failing to list its groups does not discard those of the others.
+///
+/// Brokers before Kafka 2.6 list groups without their state, and so cannot
+/// filter by state.
+///
+pub fn list_groups<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    brokers: &[BrokerMetadata],
+    states_filter: &[String],
+) -> Result<GroupsReport, ApiRequestError> {
+    let list_groups_with_state = ApiVersionQuery(ListGroupsRequest::api_key(), ListGroupsRequest::version());
+    let with_state = is_supported(api_client, bootstrap_server, &list_groups_with_state)?;
+    if !with_state && !states_filter.is_empty() {
+        return Err(ApiRequestError::from("Filtering groups by state requires brokers of Kafka 2.6 or later"));
+    }
+
+    let mut groups: BTreeMap<String, Group> = BTreeMap::new();
+    let mut failures: Vec<BrokerFailure> = vec![];
+    for broker in brokers {
+        let server_addr = bootstrap_server.with_host(&broker.host, broker.port);
+        let result = if with_state {
+            let response: Result<Response<ListGroupsResponse>, ApiRequestError> =
+                api_client.request(&server_addr, Request::of(ListGroupsRequest { states_filter: states_filter.to_vec() }));
+            response.map(|response| (response.response_message.error_code, response.response_message.groups))
+        } else {
+            let response: Result<Response<LegacyListGroupsResponse>, ApiRequestError> =
+                api_client.request(&server_addr, Request::of(LegacyListGroupsRequest {}));
+            response.map(|response| (response.response_message.error_code, response.response_message.groups))
+        };
+        let result = result.and_then(|(error_code, groups)| match error_code {
+            0 => Ok(groups),
+            error_code => Err(ApiRequestError::of(format!("ListGroups failed on broker {} with error code {}", broker.node_id, error_code))),
+        });
+        match result {
+            Ok(broker_groups) => groups.extend(broker_groups.into_iter().map(|group| (group.group_id.clone(), group))),
+            Err(error) => failures.push(BrokerFailure { broker_id: broker.node_id, error }),
+        }
+    }
+
+    Ok(GroupsReport { groups: groups.into_values().collect(), failures })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api_client::tests::{api_versions_response, MockApiClient};
+    use crate::kafka_protocol::api_verification::ApiVersionsRequest;
+
+    use super::*;
+
+    fn broker(node_id: i32) -> BrokerMetadata {
+        BrokerMetadata { node_id, host: format!("broker-{}", node_id), port: 9092, rack: None }
+    }
+
+    fn response(group_ids: &[&str]) -> Vec<u8> {
+        let groups = group_ids.iter().map(|group_id| [vec![group_id.len() as u8 + 1], group_id.as_bytes().to_vec(), vec![1, 1, 0]].concat());
+        [vec![0, 0, 0, 0, 0, 0, group_ids.len() as u8 + 1], groups.collect::<Vec<Vec<u8>>>().concat(), vec![0]].concat()
+    }
+
+    fn legacy_response(group_ids: &[&str]) -> Vec<u8> {
+        let groups = group_ids.iter().map(|group_id| [vec![0, group_id.len() as u8], group_id.as_bytes().to_vec(), vec![0, 0]].concat());
+        [vec![0, 0, 0, 0, 0, 0, 0, 0, 0, group_ids.len() as u8], groups.collect::<Vec<Vec<u8>>>().concat()].concat()
+    }
+
+    fn bootstrap_server(list_groups_version: i16) -> (KafkaServerAddr, MockApiClient) {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let api_client = MockApiClient::new().respond(
+            &bootstrap_server,
+            ApiVersionsRequest::api_key(),
+            api_versions_response(ListGroupsRequest::api_key(), list_groups_version),
+        );
+        (bootstrap_server, api_client)
+    }
+
+    #[test]
+    fn verify_list_groups() {
+        let (bootstrap_server, api_client) = bootstrap_server(4);
+        let brokers = vec![broker(1), broker(2)];
+        let api_client = api_client
+            .respond(&bootstrap_server.with_host("broker-1", 9092), ListGroupsRequest::api_key(), response(&["b", "a"]))
+            .respond(&bootstrap_server.with_host("broker-2", 9092), ListGroupsRequest::api_key(), response(&["c", "a"]));
+
+        let groups = list_groups(&api_client, &bootstrap_server, &brokers, &[]).unwrap().groups;
+        assert_eq!(vec!["a", "b", "c"], groups.iter().map(|group| group.group_id.as_str()).collect::<Vec<&str>>());
+        assert_eq!(2, api_client.requests_for(ListGroupsRequest::api_key()).len());
+    }
+
+    #[test]
+    fn verify_list_groups_before_kafka_2_6() {
+        let (bootstrap_server, api_client) = bootstrap_server(3);
+        let brokers = vec![broker(1)];
+        let api_client =
+            api_client.respond(&bootstrap_server.with_host("broker-1", 9092), ListGroupsRequest::api_key(), legacy_response(&["b", "a"]));
+
+        let groups = list_groups(&api_client, &bootstrap_server, &brokers, &[]).unwrap().groups;
+        assert_eq!(vec!["a", "b"], groups.iter().map(|group| group.group_id.as_str()).collect::<Vec<&str>>());
+        assert!(list_groups(&api_client, &bootstrap_server, &brokers, &[String::from("Empty")]).is_err());
+        assert_eq!(1, api_client.requests_for(ListGroupsRequest::api_key()).len());
+    }
+
+    #[test]
+    fn verify_list_groups_broker_failure() {
+        let (bootstrap_server, api_client) = bootstrap_server(4);
+        let brokers = vec![broker(1), broker(2)];
+        let api_client = api_client.respond(&bootstrap_server.with_host("broker-1", 9092), ListGroupsRequest::api_key(), response(&["a"]));
+
+        let report = list_groups(&api_client, &bootstrap_server, &brokers, &[]).unwrap();
+        assert_eq!(vec!["a"], report.groups.iter().map(|group| group.group_id.as_str()).collect::<Vec<&str>>());
+        assert_eq!(vec![2], report.failures.iter().map(|failure| failure.broker_id).collect::<Vec<i32>>());
+    }
+}
@@ -0,0 +1,13 @@
+pub mod acls;
+pub mod alter_configs;
+pub mod commit_offsets;
+pub(crate) mod controller;
+pub mod coordinator;
+pub mod delete_groups;
+pub mod delete_records;
+pub mod describe_log_dirs;
+pub mod elect_leaders;
+pub mod list_groups;
+pub(crate) mod partition_leaders;
+pub mod partition_reassignments;
+pub mod reset_offsets;
@@ -0,0 +1,86 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::api_client::{ApiClientTrait, ApiRequestError};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::fetch_request::IsolationLevel;
+use crate::kafka_protocol::protocol_requests::listoffsets_request;
+use crate::kafka_protocol::protocol_requests::listoffsets_request::ListOffsetsRequest;
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::listoffsets_response::ListOffsetsResponse;
+use crate::kafka_protocol::protocol_responses::metadata_response::{BrokerMetadata, PartitionMetadata, TopicMetadata};
+use crate::KafkaServerAddr;
+
+pub(crate) type TopicPartition = (String, i32);
+
+// leader -> topic -> the per partition part of a request
+pub(crate) type PartitionsByLeader<P> = BTreeMap<i32, BTreeMap<String, Vec<P>>>;
+
+/// Groups the partitions of topics by their leader, for requests (e.g.
+/// ListOffsets and DeleteRecords) that must be sent to partition leaders.
+///
+pub(crate) fn partitions_by_leader<P, F>(topics: &[TopicMetadata], partition_request: F) -> PartitionsByLeader<P>
+where
+    F: Fn(&PartitionMetadata) -> P,
+{
+    let mut partitions_by_leader: PartitionsByLeader<P> = BTreeMap::new();
+    for topic in topics {
+        for partition in &topic.partition_metadata {
+            partitions_by_leader.entry(partition.leader).or_default().entry(topic.topic.clone()).or_default().push(partition_request(partition));
+        }
+    }
+    partitions_by_leader
+}
+
+pub(crate) fn leader_server<P>(
+    bootstrap_server: &KafkaServerAddr,
+    brokers: &[BrokerMetadata],
+    leader: i32,
+    topics: &BTreeMap<String, Vec<P>>,
+) -> Result<KafkaServerAddr, ApiRequestError> {
+    brokers
+        .iter()
+        .find(|broker| broker.node_id == leader)
+        .map(|broker| bootstrap_server.with_host(&broker.host, broker.port))
+        .ok_or_else(|| ApiRequestError::of(format!("Leader {} of partitions {:?} is not available", leader, topics.keys().collect::<Vec<&String>>())))
+}
+
+/// Queries the leader of every partition for the offset of a timestamp,
+/// leaving out partitions which have no offset for it.
+///
+pub(crate) fn list_offsets<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    brokers: &[BrokerMetadata],
+    topics: &[TopicMetadata],
+    timestamp: i64,
+) -> Result<HashMap<TopicPartition, i64>, ApiRequestError> {
+    let partitions_by_leader = partitions_by_leader(topics, |partition| listoffsets_request::Partition { partition: partition.partition, timestamp });
+
+    let mut offsets = HashMap::new();
+    for (leader, topics) in partitions_by_leader {
+        let leader_server = leader_server(bootstrap_server, brokers, leader, &topics)?;
+        let request = Request::of(ListOffsetsRequest {
+            replica_id: -1,
+            isolation_level: IsolationLevel::ReadUncommitted as i8,
+            topics: topics.into_iter().map(|(topic, partitions)| listoffsets_request::Topic { topic, partitions }).collect(),
+        });
+        let response: Response<ListOffsetsResponse> = api_client.request(&leader_server, request)?;
+        for response in response.response_message.responses {
+            for partition_response in response.partition_responses {
+                match (partition_response.error_code, partition_response.offset) {
+                    (0, -1) => (),
+                    (0, offset) => {
+                        offsets.insert((response.topic.clone(), partition_response.partition), offset);
+                    }
+                    (error_code, _) => {
+                        return Err(ApiRequestError::of(format!(
+                            "ListOffsets failed for {}-{} on broker {} with error code {}",
+                            response.topic, partition_response.partition, leader, error_code
+                        )))
+                    }
+                }
+            }
+        }
+    }
+    Ok(offsets)
+}
@@ -0,0 +1,214 @@
+use crate::admin::controller::controller_server;
+use crate::api_client::{ApiClientTrait, ApiRequestError};
+use crate::kafka_protocol::api_verification::{is_supported, ApiVersionQuery, KafkaApiVersioned};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::alterpartitionreassignments_request;
+use crate::kafka_protocol::protocol_requests::alterpartitionreassignments_request::AlterPartitionReassignmentsRequest;
+use crate::kafka_protocol::protocol_requests::listpartitionreassignments_request;
+use crate::kafka_protocol::protocol_requests::listpartitionreassignments_request::ListPartitionReassignmentsRequest;
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::alterpartitionreassignments_response::AlterPartitionReassignmentsResponse;
+use crate::kafka_protocol::protocol_responses::listpartitionreassignments_response::ListPartitionReassignmentsResponse;
+use crate::kafka_protocol::protocol_responses::metadata_response::BrokerMetadata;
+use crate::KafkaServerAddr;
+
+/// A partition being reassigned, as listed by the controller
+#[derive(Clone, Debug, PartialEq)]
+pub struct OngoingReassignment {
+    pub topic: String,
+    pub partition: i32,
+    pub replicas: Vec<i32>,
+    pub adding_replicas: Vec<i32>,
+    pub removing_replicas: Vec<i32>,
+}
+
+/// Submits reassignments to the controller, where a partition with replicas is
+/// moved to them and a partition without has its ongoing reassignment cancelled.
+/// Any partition the controller rejects fails the call, although the others are
+/// still reassigned.
+///
+pub fn alter_partition_reassignments<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    brokers: &[BrokerMetadata],
+    controller_id: i32,
+    topics: Vec<alterpartitionreassignments_request::Topic>,
+    timeout: i32,
+) -> Result<(), ApiRequestError> {
+    let controller = controller_server(bootstrap_server, brokers, controller_id)?;
+    let alter_partition_reassignments = ApiVersionQuery(AlterPartitionReassignmentsRequest::api_key(), AlterPartitionReassignmentsRequest::version());
+    verify_supported(api_client, &controller, &alter_partition_reassignments, "AlterPartitionReassignments")?;
+    let response: Response<AlterPartitionReassignmentsResponse> =
+        api_client.request(&controller, Request::of(AlterPartitionReassignmentsRequest { timeout, topics }))?;
+    let response = response.response_message;
+    if response.error_code != 0 {
+        return Err(ApiRequestError::of(format!(
+            "AlterPartitionReassignments failed with error code {}: {}",
+            response.error_code,
+            response.error_message.unwrap_or_default()
+        )));
+    }
+
+    let failures = response
+        .responses
+        .iter()
+        .flat_map(|response| {
+            response.partitions.iter().filter(|partition| partition.error_code != 0).map(move |partition| {
+                format!(
+                    "{}-{} ({}: {})",
+                    response.topic,
+                    partition.partition,
+                    partition.error_code,
+                    partition.error_message.as_deref().unwrap_or_default()
+                )
+            })
+        })
+        .collect::<Vec<String>>();
+    match failures.as_slice() {
+        [] => Ok(()),
+        failures => Err(ApiRequestError::of(format!("AlterPartitionReassignments failed for {}", failures.join(", ")))),
+    }
+}
+
+/// Cancels the ongoing reassignments of the given partitions
+pub fn cancel_partition_reassignments<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    brokers: &[BrokerMetadata],
+    controller_id: i32,
+    topics: Vec<listpartitionreassignments_request::Topic>,
+    timeout: i32,
+) -> Result<(), ApiRequestError> {
+    let topics = topics
+        .into_iter()
+        .map(|topic| alterpartitionreassignments_request::Topic {
+            topic: topic.topic,
+            partitions: topic
+                .partitions
+                .into_iter()
+                .map(|partition| alterpartitionreassignments_request::Partition { partition, replicas: None })
+                .collect(),
+        })
+        .collect();
+    alter_partition_reassignments(api_client, bootstrap_server, brokers, controller_id, topics, timeout)
+}
+
+/// Lists the ongoing reassignments of the given partitions, or of every partition when None
+pub fn list_partition_reassignments<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    brokers: &[BrokerMetadata],
+    controller_id: i32,
+    topics: Option<Vec<listpartitionreassignments_request::Topic>>,
+    timeout: i32,
+) -> Result<Vec<OngoingReassignment>, ApiRequestError> {
+    let controller = controller_server(bootstrap_server, brokers, controller_id)?;
+    let list_partition_reassignments = ApiVersionQuery(ListPartitionReassignmentsRequest::api_key(), ListPartitionReassignmentsRequest::version());
+    verify_supported(api_client, &controller, &list_partition_reassignments, "ListPartitionReassignments")?;
+    let result: Result<Response<ListPartitionReassignmentsResponse>, ApiRequestError> =
+        api_client.request(&controller, Request::of(ListPartitionReassignmentsRequest { timeout, topics }));
+    result.and_then(|response| match response.response_message.error_code {
+        0 => Ok(response
+            .response_message
+            .topics
+            .into_iter()
+            .flat_map(|topic| {
+                let name = topic.topic;
+                topic.partitions.into_iter().map(move |partition| OngoingReassignment {
+                    topic: name.clone(),
+                    partition: partition.partition,
+                    replicas: partition.replicas,
+                    adding_replicas: partition.adding_replicas,
+                    removing_replicas: partition.removing_replicas,
+                })
+            })
+            .collect()),
+        error_code => Err(ApiRequestError::of(format!(
+            "ListPartitionReassignments failed with error code {}: {}",
+            error_code,
+            response.response_message.error_message.unwrap_or_default()
+        ))),
+    })
+}
+
+// the controller only handles reassignments itself since Kafka 2.4, before
+// which they are submitted through ZooKeeper
+fn verify_supported<T: ApiClientTrait>(
+    api_client: &T,
+    controller: &KafkaServerAddr,
+    query: &ApiVersionQuery,
+    api: &str,
+) -> Result<(), ApiRequestError> {
+    if is_supported(api_client, controller, query)? {
+        Ok(())
+    } else {
+        Err(ApiRequestError::of(format!("{} is not supported by the controller, which requires Kafka 2.4 or later", api)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api_client::tests::{api_versions_response, MockApiClient};
+    use crate::kafka_protocol::api_verification::ApiVersionsRequest;
+
+    use super::*;
+
+    fn brokers() -> Vec<BrokerMetadata> {
+        (1..=2).map(|node_id| BrokerMetadata { node_id, host: format!("broker-{}", node_id), port: 9092, rack: None }).collect()
+    }
+
+    #[test]
+    fn verify_cancel_partition_reassignments() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        // partition 0 is not being reassigned
+        let response = vec![0, 0, 0, 0, 0, 0, 0, 2, 2, 116, 2, 0, 0, 0, 0, 0, 85, 3, 110, 111, 0, 0, 0];
+        let controller = bootstrap_server.with_host("broker-2", 9092);
+        let api_client = MockApiClient::new()
+            .respond(&controller, ApiVersionsRequest::api_key(), api_versions_response(AlterPartitionReassignmentsRequest::api_key(), 0))
+            .respond(&controller, AlterPartitionReassignmentsRequest::api_key(), response);
+
+        let topics = vec![listpartitionreassignments_request::Topic { topic: String::from("t"), partitions: vec![0] }];
+        let error = cancel_partition_reassignments(&api_client, &bootstrap_server, &brokers(), 2, topics, 1000).unwrap_err();
+        assert!(error.error.contains("t-0 (85: no)"));
+
+        let requests = api_client.requests_for(AlterPartitionReassignmentsRequest::api_key());
+        assert_eq!(("broker-2:9092", vec![0, 0, 3, 232, 2, 2, 116, 2, 0, 0, 0, 0, 0, 0, 0, 0]), (requests[0].0.as_str(), requests[0].1.clone()));
+    }
+
+    #[test]
+    fn verify_list_partition_reassignments() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let response = vec![0, 0, 0, 0, 0, 0, 0, 2, 2, 116, 2, 0, 0, 0, 1, 3, 0, 0, 0, 1, 0, 0, 0, 2, 2, 0, 0, 0, 2, 1, 0, 0, 0, 0];
+        let controller = bootstrap_server.with_host("broker-1", 9092);
+        let api_client = MockApiClient::new()
+            .respond(&controller, ApiVersionsRequest::api_key(), api_versions_response(ListPartitionReassignmentsRequest::api_key(), 0))
+            .respond(&controller, ListPartitionReassignmentsRequest::api_key(), response);
+
+        let reassignments = list_partition_reassignments(&api_client, &bootstrap_server, &brokers(), 1, None, 1000).unwrap();
+        assert_eq!(
+            vec![OngoingReassignment {
+                topic: String::from("t"),
+                partition: 1,
+                replicas: vec![1, 2],
+                adding_replicas: vec![2],
+                removing_replicas: vec![]
+            }],
+            reassignments
+        );
+    }
+
+    #[test]
+    fn verify_partition_reassignments_before_kafka_2_4() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        // the controller only advertises ApiVersions
+        let api_client = MockApiClient::new().respond(
+            &bootstrap_server.with_host("broker-1", 9092),
+            ApiVersionsRequest::api_key(),
+            api_versions_response(ApiVersionsRequest::api_key(), 0),
+        );
+
+        let error = list_partition_reassignments(&api_client, &bootstrap_server, &brokers(), 1, None, 1000).unwrap_err();
+        assert!(error.error.contains("requires Kafka 2.4"));
+        assert!(api_client.requests_for(ListPartitionReassignmentsRequest::api_key()).is_empty());
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::admin::commit_offsets::commit_offsets;
+use crate::admin::coordinator::find_group_coordinator;
+use crate::admin::partition_leaders::{list_offsets, TopicPartition};
+use crate::api_client::{ApiClientTrait, ApiRequestError};
+use crate::kafka_protocol::api_verification::{is_supported, ApiVersionQuery, KafkaApiVersioned};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::describegroups_request::{DescribeGroupsRequest, LegacyDescribeGroupsRequest};
+use crate::kafka_protocol::protocol_requests::listoffsets_request::{EARLIEST_TIMESTAMP, LATEST_TIMESTAMP};
+use crate::kafka_protocol::protocol_requests::offsetcommit_request;
+use crate::kafka_protocol::protocol_requests::offsetfetch_request;
+use crate::kafka_protocol::protocol_requests::offsetfetch_request::OffsetFetchRequest;
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::describegroups_response::{DescribeGroupsResponse, LegacyDescribeGroupsResponse};
+use crate::kafka_protocol::protocol_responses::metadata_response::{BrokerMetadata, TopicMetadata};
+use crate::kafka_protocol::protocol_responses::offsetfetch_response::OffsetFetchResponse;
+use crate::KafkaServerAddr;
+
+/// The strategies of `kafka-consumer-groups --reset-offsets`
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResetStrategy {
+    Earliest,
+    Latest,
+    /// the earliest offset whose timestamp (milliseconds since the epoch) is at or after this one
+    ToDatetime(i64),
+    /// relative to the currently committed offset, which must exist
+    ShiftBy(i64),
+    ToOffset(i64),
+}
+
+// a group may only be reset while it has no active members
+const RESETTABLE_GROUP_STATES: [&str; 2] = ["Empty", "Dead"];
+
+// a partition without a committed offset for the group
+const NO_COMMITTED_OFFSET: i64 = -1;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartitionOffsetReset {
+    pub topic: String,
+    pub partition: i32,
+    pub current_offset: Option<i64>,
+    pub log_start_offset: i64,
+    pub log_end_offset: i64,
+    pub target_offset: i64,
+}
+
+/// The offsets a reset would commit. Displaying the plan gives the same
+/// dry-run output as `kafka-consumer-groups`, and nothing is committed until
+/// the plan is passed to `execute_offset_reset`.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct OffsetResetPlan {
+    pub group_id: String,
+    pub partitions: Vec<PartitionOffsetReset>,
+}
+
+impl Display for OffsetResetPlan {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "{:<30} {:<30} {:>10} {:>15} {:>15}", "GROUP", "TOPIC", "PARTITION", "CURRENT-OFFSET", "NEW-OFFSET")?;
+        for partition in &self.partitions {
+            let current_offset = partition.current_offset.map(|offset| offset.to_string()).unwrap_or_else(|| String::from("-"));
+            writeln!(
+                f,
+                "{:<30} {:<30} {:>10} {:>15} {:>15}",
+                self.group_id, partition.topic, partition.partition, current_offset, partition.target_offset
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes, without committing anything, the offset every partition of the given
+/// topics would be reset to, clamped to the range of offsets still in the log.
+/// Refuses to plan a reset for a group with active members.
+///
+pub fn plan_offset_reset<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    brokers: &[BrokerMetadata],
+    topics: &[TopicMetadata],
+    group_id: &str,
+    strategy: &ResetStrategy,
+) -> Result<OffsetResetPlan, ApiRequestError> {
+    let coordinator = find_group_coordinator(api_client, bootstrap_server, group_id)?;
+    verify_group_is_inactive(api_client, &coordinator, group_id)?;
+
+    let current_offsets = fetch_committed_offsets(api_client, &coordinator, topics, group_id)?;
+    let log_start_offsets = list_offsets(api_client, bootstrap_server, brokers, topics, EARLIEST_TIMESTAMP)?;
+    let log_end_offsets = list_offsets(api_client, bootstrap_server, brokers, topics, LATEST_TIMESTAMP)?;
+    let datetime_offsets = match strategy {
+        ResetStrategy::ToDatetime(timestamp) => list_offsets(api_client, bootstrap_server, brokers, topics, *timestamp)?,
+        _ => HashMap::new(),
+    };
+
+    let partitions = topics
+        .iter()
+        .flat_map(|topic| topic.partition_metadata.iter().map(move |partition| (topic.topic.clone(), partition.partition)))
+        .collect::<Vec<TopicPartition>>();
+
+    let mut resets = partitions
+        .into_iter()
+        .map(|topic_partition| {
+            let current_offset = current_offsets.get(&topic_partition).cloned();
+            let log_start_offset = log_offset(&log_start_offsets, &topic_partition, "start")?;
+            let log_end_offset = log_offset(&log_end_offsets, &topic_partition, "end")?;
+            let target_offset = match strategy {
+                ResetStrategy::Earliest => Ok(log_start_offset),
+                ResetStrategy::Latest => Ok(log_end_offset),
+                // no offset for the timestamp means every message is older than it
+                ResetStrategy::ToDatetime(_) => Ok(datetime_offsets.get(&topic_partition).cloned().unwrap_or(log_end_offset)),
+                ResetStrategy::ShiftBy(shift) => current_offset.map(|offset| offset + shift).ok_or_else(|| {
+                    ApiRequestError::of(format!(
+                        "Cannot shift the offset of {}-{} as group {} has no committed offset for it",
+                        topic_partition.0, topic_partition.1, group_id
+                    ))
+                }),
+                ResetStrategy::ToOffset(offset) => Ok(*offset),
+            }?;
+            let (topic, partition) = topic_partition;
+            Ok(PartitionOffsetReset {
+                topic,
+                partition,
+                current_offset,
+                log_start_offset,
+                log_end_offset,
+                target_offset: target_offset.max(log_start_offset).min(log_end_offset),
+            })
+        })
+        .collect::<Result<Vec<PartitionOffsetReset>, ApiRequestError>>()?;
+    resets.sort_by(|a, b| (&a.topic, a.partition).cmp(&(&b.topic, b.partition)));
+
+    Ok(OffsetResetPlan { group_id: String::from(group_id), partitions: resets })
+}
+
+/// Commits the offsets of a plan, which the coordinator rejects should the group
+/// have become active since the plan was made.
+///
+pub fn execute_offset_reset<T: ApiClientTrait>(
+    api_client: &T,
+    bootstrap_server: &KafkaServerAddr,
+    plan: OffsetResetPlan,
+) -> Result<(), ApiRequestError> {
+    let mut topics: BTreeMap<String, Vec<offsetcommit_request::Partition>> = BTreeMap::new();
+    for partition in plan.partitions {
+        topics.entry(partition.topic).or_default().push(offsetcommit_request::Partition {
+            partition: partition.partition,
+            committed_offset: partition.target_offset,
+            committed_metadata: None,
+        });
+    }
+    let topics = topics.into_iter().map(|(topic, partitions)| offsetcommit_request::Topic { topic, partitions }).collect();
+    commit_offsets(api_client, bootstrap_server, &plan.group_id, topics)
+}
+
+fn log_offset(offsets: &HashMap<TopicPartition, i64>, topic_partition: &TopicPartition, position: &str) -> Result<i64, ApiRequestError> {
+    offsets
+        .get(topic_partition)
+        .cloned()
+        .ok_or_else(|| ApiRequestError::of(format!("No log {} offset was listed for {}-{}", position, topic_partition.0, topic_partition.1)))
+}
+
+// coordinators before Kafka 2.4 are sent DescribeGroups v2
+fn verify_group_is_inactive<T: ApiClientTrait>(api_client: &T, coordinator: &KafkaServerAddr, group_id: &str) -> Result<(), ApiRequestError> {
+    let describe_groups_v4 = ApiVersionQuery(DescribeGroupsRequest::api_key(), DescribeGroupsRequest::version());
+    let groups = if is_supported(api_client, coordinator, &describe_groups_v4)? {
+        let request = Request::of(DescribeGroupsRequest { groups: vec![String::from(group_id)], include_authorized_operations: false });
+        let response: Result<Response<DescribeGroupsResponse>, ApiRequestError> = api_client.request(coordinator, request);
+        response.map(|response| response.response_message.groups)
+    } else {
+        let request = Request::of(LegacyDescribeGroupsRequest { groups: vec![String::from(group_id)] });
+        let response: Result<Response<LegacyDescribeGroupsResponse>, ApiRequestError> = api_client.request(coordinator, request);
+        response.map(|response| response.response_message.groups)
+    };
+    groups.and_then(|groups| {
+        groups
+            .into_iter()
+            .find(|group| group.group_id == group_id)
+            .ok_or_else(|| ApiRequestError::of(format!("Group {} was not described by its coordinator", group_id)))
+            .and_then(|group| match group.error_code {
+                0 if RESETTABLE_GROUP_STATES.contains(&group.group_state.as_str()) => Ok(()),
+                0 => Err(ApiRequestError::of(format!(
+                    "Group {} is {} with {} member(s) and must be empty to reset its offsets",
+                    group_id,
+                    group.group_state,
+                    group.members.len()
+                ))),
+                error_code => Err(ApiRequestError::of(format!("DescribeGroups failed for group {} with error code {}", group_id, error_code))),
+            })
+    })
+}
+
+fn fetch_committed_offsets<T: ApiClientTrait>(
+    api_client: &T,
+    coordinator: &KafkaServerAddr,
+    topics: &[TopicMetadata],
+    group_id: &str,
+) -> Result<HashMap<TopicPartition, i64>, ApiRequestError> {
+    let request = Request::of(OffsetFetchRequest {
+        group_id: String::from(group_id),
+        topics: topics
+            .iter()
+            .map(|topic| offsetfetch_request::Topic {
+                topic: topic.topic.clone(),
+                partitions: topic.partition_metadata.iter().map(|partition| partition.partition).collect(),
+            })
+            .collect(),
+    });
+    let result: Result<Response<OffsetFetchResponse>, ApiRequestError> = api_client.request(coordinator, request);
+    result.and_then(|response| match response.response_message.error_code {
+        0 => {
+            let mut offsets = HashMap::new();
+            for response in response.response_message.responses {
+                for partition_response in response.partition_responses {
+                    match (partition_response.error_code, partition_response.offset) {
+                        (0, NO_COMMITTED_OFFSET) => (),
+                        (0, offset) => {
+                            offsets.insert((response.topic.clone(), partition_response.partition), offset);
+                        }
+                        (error_code, _) => {
+                            return Err(ApiRequestError::of(format!(
+                                "OffsetFetch failed for {}-{} with error code {}",
+                                response.topic, partition_response.partition, error_code
+                            )))
+                        }
+                    }
+                }
+            }
+            Ok(offsets)
+        }
+        error_code => Err(ApiRequestError::of(format!("OffsetFetch failed for group {} with error code {}", group_id, error_code))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::admin::coordinator::tests::coordinator_response;
+    use crate::api_client::tests::{api_versions_response, MockApiClient};
+    use crate::kafka_protocol::api_verification::ApiVersionsRequest;
+    use crate::kafka_protocol::protocol_requests::findcoordinator_request::FindCoordinatorRequest;
+    use crate::kafka_protocol::protocol_requests::listoffsets_request::ListOffsetsRequest;
+    use crate::kafka_protocol::protocol_requests::offsetcommit_request::OffsetCommitRequest;
+    use crate::kafka_protocol::protocol_responses::metadata_response::PartitionMetadata;
+
+    use super::*;
+
+    fn string(value: &str) -> Vec<u8> {
+        [vec![0, value.len() as u8], value.as_bytes().to_vec()].concat()
+    }
+
+    // only version 4 has group_instance_id and authorized_operations
+    fn describe_groups_response(group_state: &str, member_count: u8, version: i16) -> Vec<u8> {
+        let group_instance_id = if version == 4 { vec![255, 255] } else { vec![] };
+        let member = [string("m"), group_instance_id, string("c"), string("h"), vec![0, 0, 0, 0, 0, 0, 0, 0]].concat();
+        [
+            vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 0],
+            string("g"),
+            string(group_state),
+            string("consumer"),
+            string("range"),
+            vec![0, 0, 0, member_count],
+            (0..member_count).map(|_| member.clone()).collect::<Vec<Vec<u8>>>().concat(),
+            if version == 4 { vec![0, 0, 0, 0] } else { vec![] },
+        ]
+        .concat()
+    }
+
+    // (partition, offset) of topic t
+    fn offset_fetch_response(offsets: &[(u8, i64)]) -> Vec<u8> {
+        let partitions =
+            offsets.iter().map(|(partition, offset)| [vec![0, 0, 0, *partition], offset.to_be_bytes().to_vec(), vec![255, 255, 0, 0]].concat());
+        [vec![0, 0, 0, 0, 0, 0, 0, 1], string("t"), vec![0, 0, 0, offsets.len() as u8], partitions.collect::<Vec<Vec<u8>>>().concat(), vec![0, 0]]
+            .concat()
+    }
+
+    fn list_offsets_response(offsets: &[(u8, i64)]) -> Vec<u8> {
+        let partitions = offsets
+            .iter()
+            .map(|(partition, offset)| [vec![0, 0, 0, *partition, 0, 0], (-1i64).to_be_bytes().to_vec(), offset.to_be_bytes().to_vec()].concat());
+        [vec![0, 0, 0, 0, 0, 0, 0, 1], string("t"), vec![0, 0, 0, offsets.len() as u8], partitions.collect::<Vec<Vec<u8>>>().concat()].concat()
+    }
+
+    fn topics() -> Vec<TopicMetadata> {
+        let partition =
+            |partition| PartitionMetadata { error_code: 0, partition, leader: 1, replicas: vec![1], isr: vec![1], offline_replicas: vec![] };
+        vec![TopicMetadata { error_code: 0, topic: String::from("t"), is_internal: false, partition_metadata: vec![partition(0), partition(1)] }]
+    }
+
+    fn api_client(bootstrap_server: &KafkaServerAddr, group_state: &str, member_count: u8, describe_groups_version: i16) -> MockApiClient {
+        let broker = bootstrap_server.with_host("broker-1", 9092);
+        MockApiClient::new()
+            .respond(bootstrap_server, FindCoordinatorRequest::api_key(), coordinator_response(0, "broker-1"))
+            .respond(bootstrap_server, FindCoordinatorRequest::api_key(), coordinator_response(0, "broker-1"))
+            .respond(&broker, ApiVersionsRequest::api_key(), api_versions_response(DescribeGroupsRequest::api_key(), describe_groups_version))
+            .respond(&broker, DescribeGroupsRequest::api_key(), describe_groups_response(group_state, member_count, describe_groups_version))
+            .respond(&broker, OffsetFetchRequest::api_key(), offset_fetch_response(&[(0, 20), (1, 45)]))
+            .respond(&broker, ListOffsetsRequest::api_key(), list_offsets_response(&[(0, 10), (1, 0)]))
+            .respond(&broker, ListOffsetsRequest::api_key(), list_offsets_response(&[(0, 100), (1, 50)]))
+            .respond(&broker, OffsetCommitRequest::api_key(), vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 116, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0])
+    }
+
+    #[test]
+    fn verify_shift_by_offset_reset() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let brokers = vec![BrokerMetadata { node_id: 1, host: String::from("broker-1"), port: 9092, rack: None }];
+        let api_client = api_client(&bootstrap_server, "Empty", 0, 4);
+
+        let plan = plan_offset_reset(&api_client, &bootstrap_server, &brokers, &topics(), "g", &ResetStrategy::ShiftBy(-15)).unwrap();
+        assert_eq!(
+            vec![
+                // clamped to the log start offset
+                PartitionOffsetReset {
+                    topic: String::from("t"),
+                    partition: 0,
+                    current_offset: Some(20),
+                    log_start_offset: 10,
+                    log_end_offset: 100,
+                    target_offset: 10
+                },
+                PartitionOffsetReset {
+                    topic: String::from("t"),
+                    partition: 1,
+                    current_offset: Some(45),
+                    log_start_offset: 0,
+                    log_end_offset: 50,
+                    target_offset: 30
+                },
+            ],
+            plan.partitions
+        );
+        assert!(api_client.requests_for(OffsetCommitRequest::api_key()).is_empty());
+
+        execute_offset_reset(&api_client, &bootstrap_server, plan).unwrap();
+        let requests = api_client.requests_for(OffsetCommitRequest::api_key());
+        assert_eq!(1, requests.len());
+        assert_eq!("broker-1:9092", requests[0].0);
+    }
+
+    #[test]
+    fn verify_offset_reset_refused_for_active_group() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let brokers = vec![BrokerMetadata { node_id: 1, host: String::from("broker-1"), port: 9092, rack: None }];
+        let api_client = api_client(&bootstrap_server, "Stable", 1, 4);
+
+        let error = plan_offset_reset(&api_client, &bootstrap_server, &brokers, &topics(), "g", &ResetStrategy::Earliest).unwrap_err();
+        assert!(error.error.contains("must be empty"));
+        assert!(api_client.requests_for(ListOffsetsRequest::api_key()).is_empty());
+    }
+
+    #[test]
+    fn verify_offset_reset_before_kafka_2_4() {
+        let bootstrap_server = KafkaServerAddr::of(String::from("bootstrap"), 9092, false);
+        let brokers = vec![BrokerMetadata { node_id: 1, host: String::from("broker-1"), port: 9092, rack: None }];
+        let api_client = api_client(&bootstrap_server, "Stable", 1, 3);
+
+        let error = plan_offset_reset(&api_client, &bootstrap_server, &brokers, &topics(), "g", &ResetStrategy::Earliest).unwrap_err();
+        assert!(error.error.contains("Stable with 1 member(s)"));
+        // a DescribeGroups v2 request only has the group ids
+        let requests = api_client.requests_for(DescribeGroupsRequest::api_key());
+        assert_eq!(vec![0, 0, 0, 1, 0, 1, 103], requests[0].1);
+    }
+}
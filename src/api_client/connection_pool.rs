@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::io;
+use std::io::{ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+
+use crate::api_client::sasl::SaslMechanism;
+use crate::api_client::tls::ClientIdentity;
+#[cfg(any(feature = "native-tls", feature = "rustls"))]
+use crate::api_client::tls::TlsStream;
+use crate::KafkaServerAddr;
+
+/// An established, and if required authenticated, connection to a broker,
+/// numbering the requests sent on it with increasing correlation ids.
+///
+pub(crate) struct Connection<S = Stream> {
+    stream: S,
+    next_correlation_id: i32,
+}
+
+pub(crate) enum Stream {
+    Plaintext(TcpStream),
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    Tls(Box<TlsStream>),
+}
+
+impl<S: Read + Write> Connection<S> {
+    pub fn new(stream: S) -> Connection<S> {
+        Connection { stream, next_correlation_id: 0 }
+    }
+
+    /// Correlation ids restart from 0 after i32::MAX, as they must not be negative
+    pub fn next_correlation_id(&mut self) -> i32 {
+        let correlation_id = self.next_correlation_id;
+        self.next_correlation_id = correlation_id.checked_add(1).unwrap_or(0);
+        correlation_id
+    }
+
+    pub fn stream(&mut self) -> &mut S {
+        &mut self.stream
+    }
+}
+
+impl Connection<Stream> {
+    fn tcp_stream(&self) -> &TcpStream {
+        match self.stream {
+            Stream::Plaintext(ref stream) => stream,
+            #[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+            Stream::Tls(ref stream) => stream.get_ref(),
+            #[cfg(feature = "rustls")]
+            Stream::Tls(ref stream) => &stream.sock,
+        }
+    }
+
+    /// An idle connection has nothing to read until it is sent a request, so
+    /// anything other than a read that would block means the broker closed it
+    /// (e.g. after its connections.max.idle.ms) or the connection is out of sync.
+    ///
+    fn is_alive(&self) -> bool {
+        let stream = self.tcp_stream();
+        let mut buf = [0u8; 1];
+        let alive =
+            stream.set_nonblocking(true).and_then(|_| stream.peek(&mut buf)).map_or_else(|err| err.kind() == ErrorKind::WouldBlock, |_| false);
+        stream.set_nonblocking(false).is_ok() && alive
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Stream::Plaintext(stream) => stream.read(buf),
+            #[cfg(any(feature = "native-tls", feature = "rustls"))]
+            Stream::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Stream::Plaintext(stream) => stream.write(buf),
+            #[cfg(any(feature = "native-tls", feature = "rustls"))]
+            Stream::Tls(stream) => stream.write(buf),
+        }
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Stream::Plaintext(stream) => stream.flush(),
+            #[cfg(any(feature = "native-tls", feature = "rustls"))]
+            Stream::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Identifies the connections which can be reused for a `KafkaServerAddr`:
+/// those to the same broker, connected to and authenticated the same way. The
+/// key is a SHA-256 digest of every field, so that the pool keeps no
+/// credentials, with an OAuth bearer token provider identified by its address.
+///
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct ConnectionKey([u8; 32]);
+
+impl ConnectionKey {
+    pub fn of(server_addr: &KafkaServerAddr) -> ConnectionKey {
+        let mut digest = KeyDigest(Sha256::new());
+        digest.field(server_addr.domain.as_bytes());
+        digest.field(&server_addr.port.to_be_bytes());
+        digest.field(&[server_addr.use_tls as u8]);
+
+        let tls_config = &server_addr.tls_config;
+        match tls_config.ca_certificates {
+            Some(ref ca_certificates) => digest.tag(1).path(ca_certificates),
+            None => digest.tag(0),
+        };
+        match tls_config.client_identity {
+            Some(ClientIdentity::Pem { ref certificate, ref key }) => digest.tag(1).path(certificate).path(key),
+            Some(ClientIdentity::Pkcs12 { ref archive, ref password }) => digest.tag(2).path(archive).field(password.as_bytes()),
+            None => digest.tag(0),
+        };
+        digest.field(&[tls_config.accept_invalid_certs as u8, tls_config.accept_invalid_hostnames as u8]);
+        digest.tag(tls_config.min_protocol_version.map(|version| version as u8 + 1).unwrap_or(0));
+
+        match server_addr.sasl {
+            Some(SaslMechanism::Plain { ref username, ref password }) => digest.tag(1).field(username.as_bytes()).field(password.as_bytes()),
+            Some(SaslMechanism::ScramSha256 { ref username, ref password }) => digest.tag(2).field(username.as_bytes()).field(password.as_bytes()),
+            Some(SaslMechanism::ScramSha512 { ref username, ref password }) => digest.tag(3).field(username.as_bytes()).field(password.as_bytes()),
+            Some(SaslMechanism::OAuthBearer { ref token_provider, ref extensions }) => {
+                digest.tag(4).field(&(Arc::as_ptr(token_provider) as *const () as usize).to_be_bytes());
+                extensions.iter().fold(&mut digest, |digest, (name, value)| digest.field(name.as_bytes()).field(value.as_bytes()))
+            }
+            None => digest.tag(0),
+        };
+
+        ConnectionKey(digest.0.finalize().into())
+    }
+}
+
+// each field is prefixed with its length, so that adjacent fields can't run into each other
+struct KeyDigest(Sha256);
+
+impl KeyDigest {
+    fn field(&mut self, bytes: &[u8]) -> &mut KeyDigest {
+        self.0.update((bytes.len() as u64).to_be_bytes());
+        self.0.update(bytes);
+        self
+    }
+
+    fn tag(&mut self, tag: u8) -> &mut KeyDigest {
+        self.field(&[tag])
+    }
+
+    fn path(&mut self, path: &Path) -> &mut KeyDigest {
+        self.field(path.as_os_str().as_encoded_bytes())
+    }
+}
+
+struct IdleConnection {
+    connection: Connection,
+    idle_since: Instant,
+}
+
+/// Idle connections, by the broker (and how it is connected to), which are
+/// reused by later requests unless they were closed or have been idle for
+/// longer than `idle_timeout`.
+///
+pub(crate) struct ConnectionPool {
+    idle_timeout: Duration,
+    idle_connections: Mutex<HashMap<ConnectionKey, Vec<IdleConnection>>>,
+}
+
+impl ConnectionPool {
+    pub fn new(idle_timeout: Duration) -> ConnectionPool {
+        ConnectionPool { idle_timeout, idle_connections: Mutex::new(HashMap::new()) }
+    }
+
+    /// Takes a live, idle connection, if there is one, after evicting every
+    /// connection idle for longer than the timeout
+    pub fn checkout(&self, key: &ConnectionKey) -> Option<Connection> {
+        let idle_timeout = self.idle_timeout;
+        let mut idle_connections = self.idle_connections.lock().ok()?;
+        idle_connections.values_mut().for_each(|connections| connections.retain(|idle| idle.idle_since.elapsed() <= idle_timeout));
+        idle_connections.retain(|_, connections| !connections.is_empty());
+
+        let connections = idle_connections.get_mut(key)?;
+        let mut live_connection = None;
+        while let Some(IdleConnection { connection, .. }) = connections.pop() {
+            if connection.is_alive() {
+                live_connection = Some(connection);
+                break;
+            }
+        }
+        live_connection
+    }
+
+    /// Returns a connection, after it has been sent a request and read its response
+    pub fn checkin(&self, key: ConnectionKey, connection: Connection) {
+        if let Ok(mut idle_connections) = self.idle_connections.lock() {
+            idle_connections.entry(key).or_default().push(IdleConnection { connection, idle_since: Instant::now() });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+
+    use crate::api_client::sasl::OAuthBearerTokenProvider;
+    use crate::api_client::tls::TlsConfig;
+    use crate::api_client::ApiRequestError;
+
+    use super::*;
+
+    fn key(domain: &str) -> ConnectionKey {
+        ConnectionKey::of(&KafkaServerAddr::of(String::from(domain), 9092, false))
+    }
+
+    fn connection(listener: &TcpListener) -> (Connection, TcpStream) {
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        (Connection::new(Stream::Plaintext(client)), server)
+    }
+
+    fn local_port(connection: &Connection) -> u16 {
+        connection.tcp_stream().local_addr().unwrap().port()
+    }
+
+    #[test]
+    fn verify_connection_reuse() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let pool = ConnectionPool::new(Duration::from_secs(60));
+        let (connection, _server) = connection(&listener);
+        let port = local_port(&connection);
+
+        pool.checkin(key("broker"), connection);
+        assert!(pool.checkout(&key("another-broker")).is_none());
+        let connection = pool.checkout(&key("broker")).unwrap();
+        assert_eq!(port, local_port(&connection));
+        assert!(pool.checkout(&key("broker")).is_none());
+    }
+
+    #[test]
+    fn verify_closed_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let pool = ConnectionPool::new(Duration::from_secs(60));
+        let (closed, server) = connection(&listener);
+        let (live, _server) = connection(&listener);
+        let port = local_port(&live);
+
+        pool.checkin(key("broker"), live);
+        pool.checkin(key("broker"), closed);
+        drop(server);
+        std::thread::sleep(Duration::from_millis(50));
+
+        let connection = pool.checkout(&key("broker")).unwrap();
+        assert_eq!(port, local_port(&connection));
+    }
+
+    #[test]
+    fn verify_idle_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let pool = ConnectionPool::new(Duration::from_millis(10));
+        let (connection, _server) = connection(&listener);
+
+        pool.checkin(key("broker"), connection);
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(pool.checkout(&key("broker")).is_none());
+        assert!(pool.idle_connections.lock().unwrap().is_empty());
+    }
+
+    struct Token;
+
+    impl OAuthBearerTokenProvider for Token {
+        fn token(&self) -> Result<String, ApiRequestError> {
+            Ok(String::from("token"))
+        }
+    }
+
+    #[test]
+    fn verify_connection_keys() {
+        let server_addr = KafkaServerAddr::of(String::from("broker"), 9092, false);
+
+        // principals sharing a username, which is all their Debug output shows
+        let alice = server_addr.clone().with_sasl(SaslMechanism::scram_sha_512("user", "alice's password"));
+        let bob = server_addr.clone().with_sasl(SaslMechanism::scram_sha_512("user", "bob's password"));
+        assert_eq!(format!("{:?}", alice), format!("{:?}", bob));
+        assert_ne!(ConnectionKey::of(&alice), ConnectionKey::of(&bob));
+        assert_eq!(ConnectionKey::of(&alice), ConnectionKey::of(&alice.clone()));
+        assert_ne!(ConnectionKey::of(&alice), ConnectionKey::of(&server_addr.clone().with_sasl(SaslMechanism::plain("user", "alice's password"))));
+
+        let token_provider: Arc<dyn OAuthBearerTokenProvider> = Arc::new(Token);
+        let oauth_bearer = |token_provider: &Arc<dyn OAuthBearerTokenProvider>| {
+            ConnectionKey::of(&server_addr.clone().with_sasl(SaslMechanism::oauth_bearer(token_provider.clone(), BTreeMap::new())))
+        };
+        assert_eq!(oauth_bearer(&token_provider), oauth_bearer(&token_provider));
+        let another_token_provider: Arc<dyn OAuthBearerTokenProvider> = Arc::new(Token);
+        assert_ne!(oauth_bearer(&token_provider), oauth_bearer(&another_token_provider));
+
+        let pkcs12 = |password: &str| {
+            let client_identity = ClientIdentity::Pkcs12 { archive: PathBuf::from("client.p12"), password: String::from(password) };
+            ConnectionKey::of(&server_addr.clone().with_tls(TlsConfig { client_identity: Some(client_identity), ..TlsConfig::default() }))
+        };
+        assert_ne!(pkcs12("alice's password"), pkcs12("bob's password"));
+        assert_ne!(ConnectionKey::of(&server_addr), ConnectionKey::of(&server_addr.with_tls(TlsConfig::default())));
+    }
+}
@@ -1,21 +1,43 @@
+mod connection_pool;
+mod oauthbearer;
+pub mod sasl;
+mod scram;
+pub mod tls;
+
 use std::fmt;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::io::{Cursor, Read, Write};
 use std::net::*;
+use std::sync::Arc;
+use std::time::Duration;
 
 use byteorder::{BigEndian, ReadBytesExt};
-use native_tls::TlsConnector;
 
+use crate::api_client::connection_pool::{Connection, ConnectionKey, ConnectionPool, Stream};
 use crate::kafka_protocol::protocol_request::*;
 use crate::kafka_protocol::protocol_response::*;
 use crate::kafka_protocol::protocol_serializable::*;
 use crate::KafkaServerAddr;
 use crate::IO;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ApiRequestError {
     pub error: String,
+    pub kind: ApiRequestErrorKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ApiRequestErrorKind {
+    Other,
+    /// the connection failed (e.g. was closed by the broker) while the request was written, so it
+    /// was not sent in full and cannot have been handled
+    NotSent,
+    /// a response answering another request than the one sent, after which the connection is closed
+    CorrelationIdMismatch {
+        expected: i32,
+        received: i32,
+    },
 }
 
 impl Display for ApiRequestError {
@@ -26,7 +48,16 @@ impl Display for ApiRequestError {
 
 impl ApiRequestError {
     pub fn of(error: String) -> ApiRequestError {
-        ApiRequestError { error }
+        ApiRequestError { error, kind: ApiRequestErrorKind::Other }
+    }
+    pub fn correlation_id_mismatch(expected: i32, received: i32) -> ApiRequestError {
+        ApiRequestError {
+            error: format!("Response correlation id {} does not match the request's {}", received, expected),
+            kind: ApiRequestErrorKind::CorrelationIdMismatch { expected, received },
+        }
+    }
+    pub fn not_sent(error: String) -> ApiRequestError {
+        ApiRequestError { error, kind: ApiRequestErrorKind::NotSent }
     }
     pub fn from(error: &str) -> ApiRequestError {
         ApiRequestError::of(String::from(error))
@@ -40,14 +71,34 @@ pub trait ApiClientTrait {
         Vec<u8>: ProtocolDeserializable<Response<U>>;
 }
 
-pub type ApiClientProvider<T> = Box<Fn() -> IO<T, ApiRequestError>>;
+pub type ApiClientProvider<T> = Box<dyn Fn() -> IO<T, ApiRequestError>>;
 
+// a little less than the default connections.max.idle.ms of brokers (10 minutes),
+// so that idle connections are evicted before the broker closes them
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(9 * 60);
+
+/// Sends requests over connections which are kept open, and reused, for
+/// each broker. Clones share the same connections.
+///
 #[derive(Clone)]
-pub struct ApiClient {}
+pub struct ApiClient {
+    connection_pool: Arc<ConnectionPool>,
+}
+
+impl Default for ApiClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl ApiClient {
     pub fn new() -> ApiClient {
-        ApiClient {}
+        ApiClient::with_idle_timeout(DEFAULT_IDLE_TIMEOUT)
+    }
+
+    /// Closes connections left idle for longer than `idle_timeout`
+    pub fn with_idle_timeout(idle_timeout: Duration) -> ApiClient {
+        ApiClient { connection_pool: Arc::new(ConnectionPool::new(idle_timeout)) }
     }
 }
 
@@ -57,49 +108,352 @@ impl ApiClientTrait for ApiClient {
         T: ProtocolSerializable,
         Vec<u8>: ProtocolDeserializable<Response<U>>,
     {
-        let _api_key = request.header.api_key;
-        let _api_key_version = request.header.api_version;
-        let request_bytes = request.into_protocol_bytes().map_err(|err| ApiRequestError::of(format!("Could not serialize request. {}", err)));
+        // serialized up front, so that the request can be sent again on another connection
+        let Request { header, request_message } = request;
+        let request_message =
+            request_message.into_protocol_bytes().map_err(|err| ApiRequestError::of(format!("Could not serialize request. {}", err)))?;
+        let request = Request { header, request_message: SerializedMessage(request_message) };
+
+        let connection_key = ConnectionKey::of(server_addr);
+        let (connection, response) = match self.connection_pool.checkout(&connection_key) {
+            Some(mut connection) => match send(&mut connection, request.clone()) {
+                // the broker may have closed the idle connection (e.g. after its connections.max.idle.ms)
+                // without checkout noticing, so it is replaced once. Once written, a request is never sent
+                // again, as the broker may have handled it (e.g. a Produce) before the connection failed.
+                Err(ref err) if err.kind == ApiRequestErrorKind::NotSent => send_on_new_connection(server_addr, request)?,
+                response => (connection, response),
+            },
+            None => send_on_new_connection(server_addr, request)?,
+        };
+
+        // a connection is only reused once its response has been read in full
+        if response.is_ok() {
+            self.connection_pool.checkin(connection_key, connection);
+        }
+        response
+    }
+}
+
+#[derive(Clone)]
+struct SerializedMessage(Vec<u8>);
+
+impl ProtocolSerializable for SerializedMessage {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        Ok(self.0)
+    }
+}
+
+type SentRequest<U> = (Connection, Result<Response<U>, ApiRequestError>);
+
+fn send_on_new_connection<U>(server_addr: &KafkaServerAddr, request: Request<SerializedMessage>) -> Result<SentRequest<U>, ApiRequestError>
+where
+    Vec<u8>: ProtocolDeserializable<Response<U>>,
+{
+    let mut connection = connect(server_addr)?;
+    let response = send(&mut connection, request);
+    Ok((connection, response))
+}
+
+fn connect(server_addr: &KafkaServerAddr) -> Result<Connection, ApiRequestError> {
+    let stream = TcpStream::connect(server_addr.as_socket_addr()).map_err(|err| ApiRequestError::of(err.to_string()))?;
+    let mut connection = match server_addr.use_tls {
+        false => Ok(Stream::Plaintext(stream)),
+        #[cfg(any(feature = "native-tls", feature = "rustls"))]
+        true => tls::connect(&server_addr.tls_config, server_addr.domain.as_str(), stream).map(|stream| Stream::Tls(Box::new(stream))),
+        #[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+        true => Err(ApiRequestError::from("TLS requires the 'native-tls' or 'rustls' feature of topiks-kafka-client, neither of which is enabled")),
+    }
+    .map(Connection::new)?;
+    match server_addr.sasl {
+        Some(ref mechanism) => sasl::authenticate(&mut connection, mechanism).map(|_| connection),
+        None => Ok(connection),
+    }
+}
+
+/// Sends a request over an established (and, if required, authenticated)
+/// connection with the connection's next correlation id, and reads its
+/// response, which must have the same correlation id.
+///
+pub(crate) fn send<S, T, U>(connection: &mut Connection<S>, request: Request<T>) -> Result<Response<U>, ApiRequestError>
+where
+    S: Read + Write,
+    T: ProtocolSerializable,
+    Vec<u8>: ProtocolDeserializable<Response<U>>,
+{
+    let mut request = request;
+    let correlation_id = connection.next_correlation_id();
+    request.header.correlation_id = correlation_id;
+    let stream = connection.stream();
+
+    let _api_key = request.header.api_key;
+    let _api_key_version = request.header.api_version;
+    let flexible = request.header.flexible;
+    let request_bytes = request.into_protocol_bytes().map_err(|err| ApiRequestError::of(format!("Could not serialize request. {}", err)));
+
+    fn write_bytes<S: Write>(stream: &mut S, bytes: Vec<u8>) -> Result<(), ApiRequestError> {
+        stream.write_all(bytes.as_slice()).and_then(|_| stream.flush()).map_err(|err| ApiRequestError::not_sent(err.to_string()))
+    }
+
+    fn read_bytes<S: Read>(stream: &mut S) -> Result<Vec<u8>, ApiRequestError> {
+        let mut result_size_buf: [u8; 4] = [0; 4];
+        stream
+            .read_exact(&mut result_size_buf)
+            .and_then(|_| Cursor::new(result_size_buf.to_vec()).read_i32::<BigEndian>())
+            .and_then(|result_size| {
+                let mut message_buf: Vec<u8> = vec![0; result_size as usize];
+                stream.read_exact(&mut message_buf).map(|_| message_buf)
+            })
+            .map_err(|err| ApiRequestError::of(err.to_string()))
+    }
+
+    fn verify_correlation_id(bytes: Vec<u8>, correlation_id: i32) -> Result<Vec<u8>, ApiRequestError> {
+        match bytes.get(0..4).map(de_i32) {
+            Some(Ok(received)) if received == correlation_id => Ok(bytes),
+            Some(Ok(received)) => Err(ApiRequestError::correlation_id_mismatch(correlation_id, received)),
+            _ => Err(ApiRequestError::from("Response too small for header")),
+        }
+    }
+
+    let response = request_bytes
+        .and_then(|bytes| write_bytes(stream, bytes).and_then(|_| read_bytes(stream)))
+        .and_then(|bytes| verify_correlation_id(bytes, correlation_id));
+
+    // DEBUG response
+    //        response.iter().for_each(|bytes| {
+    //            println!("bytes for API key {}:v{}: {:?}", _api_key, _api_key_version, ::to_hex_array(&bytes));
+    //        });
+
+    let response = response.and_then(|bytes| match flexible {
+        true => without_header_tagged_fields(bytes).map_err(|e| ApiRequestError::of(e.error)),
+        false => Ok(bytes),
+    });
+
+    response.and_then(|bytes| bytes.into_protocol_type().map_err(|e| ApiRequestError::of(e.error)))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::cell::RefCell;
+    use std::collections::{HashMap, VecDeque};
+    use std::io;
+    use std::io::ErrorKind;
+    use std::sync::mpsc;
+    use std::thread;
 
-        fn tcp_stream(server_addr: &KafkaServerAddr) -> Result<TcpStream, ApiRequestError> {
-            TcpStream::connect(server_addr.as_socket_addr()).map_err(|err| ApiRequestError::of(err.to_string()))
+    use crate::kafka_protocol::api_verification::{ApiVersionResponse, ApiVersionsRequest};
+
+    use super::*;
+
+    // (server address, API key) -> response messages
+    type CannedResponses = HashMap<(String, i16), VecDeque<Vec<u8>>>;
+
+    /// Responds to requests with canned response messages (without their header),
+    /// queued by server address and API key, and records each request sent.
+    ///
+    pub(crate) struct MockApiClient {
+        responses: RefCell<CannedResponses>,
+        pub requests: RefCell<Vec<(String, i16, Vec<u8>)>>,
+    }
+
+    impl MockApiClient {
+        pub fn new() -> MockApiClient {
+            MockApiClient { responses: RefCell::new(HashMap::new()), requests: RefCell::new(vec![]) }
+        }
+
+        pub fn respond(self, server_addr: &KafkaServerAddr, api_key: i16, response_message: Vec<u8>) -> MockApiClient {
+            self.responses.borrow_mut().entry((server_addr.as_socket_addr(), api_key)).or_default().push_back(response_message);
+            self
+        }
+
+        /// The request messages (without their header) sent for an API key
+        pub fn requests_for(&self, api_key: i16) -> Vec<(String, Vec<u8>)> {
+            self.requests
+                .borrow()
+                .iter()
+                .filter(|(_, key, _)| *key == api_key)
+                .map(|(server_addr, _, bytes)| (server_addr.clone(), bytes.clone()))
+                .collect()
         }
+    }
+
+    impl ApiClientTrait for MockApiClient {
+        fn request<T, U>(&self, server_addr: &KafkaServerAddr, request: Request<T>) -> Result<Response<U>, ApiRequestError>
+        where
+            T: ProtocolSerializable,
+            Vec<u8>: ProtocolDeserializable<Response<U>>,
+        {
+            let api_key = request.header.api_key;
+            let request_message = request.request_message.into_protocol_bytes().map_err(|e| ApiRequestError::of(e.to_string()))?;
+            self.requests.borrow_mut().push((server_addr.as_socket_addr(), api_key, request_message));
 
-        fn write_bytes<S: Write>(stream: &mut S, bytes: Vec<u8>) -> Result<usize, ApiRequestError> {
-            stream.write(bytes.as_slice()).map_err(|err| ApiRequestError::of(err.to_string()))
+            let response_message = self.responses.borrow_mut().get_mut(&(server_addr.as_socket_addr(), api_key)).and_then(|queue| queue.pop_front());
+            response_message
+                .ok_or_else(|| ApiRequestError::of(format!("No response for API key {} from {}", api_key, server_addr.as_socket_addr())))
+                .and_then(|bytes| [vec![0, 0, 0, 42], bytes].concat().into_protocol_type().map_err(|e| ApiRequestError::of(e.error)))
         }
+    }
 
-        fn read_bytes<S: Read>(stream: &mut S) -> Result<Vec<u8>, ApiRequestError> {
-            let mut result_size_buf: [u8; 4] = [0; 4];
-            stream
-                .read(&mut result_size_buf)
-                .and_then(|_| Cursor::new(result_size_buf.to_vec()).read_i32::<BigEndian>())
-                .and_then(|result_size| {
-                    let mut message_buf: Vec<u8> = vec![0; result_size as usize];
-                    stream.read_exact(&mut message_buf).map(|_| message_buf)
+    /// An ApiVersionResponse advertising versions 0 to max_version of an API
+    ///
+    pub(crate) fn api_versions_response(api_key: i16, max_version: i16) -> Vec<u8> {
+        [vec![0, 0, 0, 0, 0, 1], api_key.to_be_bytes().to_vec(), vec![0, 0], max_version.to_be_bytes().to_vec()].concat()
+    }
+
+    /// A stream reading canned response messages, framed with their size and
+    /// the correlation ids of a new connection, and recording everything written to it.
+    ///
+    pub(crate) struct MockStream {
+        pub input: Cursor<Vec<u8>>,
+        pub output: Vec<u8>,
+    }
+
+    impl MockStream {
+        pub fn of(response_messages: Vec<Vec<u8>>) -> MockStream {
+            let input = response_messages
+                .into_iter()
+                .enumerate()
+                .map(|(correlation_id, message)| {
+                    [((message.len() + 4) as i32).to_be_bytes().to_vec(), (correlation_id as i32).to_be_bytes().to_vec(), message].concat()
                 })
-                .map_err(|err| ApiRequestError::of(err.to_string()))
+                .collect::<Vec<Vec<u8>>>()
+                .concat();
+            MockStream { input: Cursor::new(input), output: vec![] }
+        }
+    }
+
+    impl Read for MockStream {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for MockStream {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.output.write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
         }
+    }
+
+    #[test]
+    fn verify_correlation_ids() {
+        let mut connection = Connection::new(MockStream::of(vec![vec![0, 0, 0, 0, 0, 0], vec![0, 0, 0, 0, 0, 0]]));
+        for correlation_id in 0..2 {
+            let response: Response<ApiVersionResponse> = send(&mut connection, Request::of(ApiVersionsRequest {})).unwrap();
+            assert_eq!(correlation_id, response.header.correlation_id);
+        }
+        let output = &connection.stream().output;
+        assert_eq!(vec![0, 0, 0, 1], output[output.len() - 12..output.len() - 8].to_vec());
+    }
+
+    #[test]
+    fn verify_correlation_id_mismatch() {
+        // answering the second request first
+        let input = [vec![0, 0, 0, 10, 0, 0, 0, 1], vec![0; 6]].concat();
+        let mut connection = Connection::new(MockStream { input: Cursor::new(input), output: vec![] });
+        let error = send::<_, _, ApiVersionResponse>(&mut connection, Request::of(ApiVersionsRequest {})).unwrap_err();
+        assert_eq!(ApiRequestErrorKind::CorrelationIdMismatch { expected: 0, received: 1 }, error.kind);
+    }
+
+    fn read_request(stream: &mut TcpStream) -> Vec<u8> {
+        let mut size = [0u8; 4];
+        stream.read_exact(&mut size).unwrap();
+        let mut request = vec![0u8; i32::from_be_bytes(size) as usize];
+        stream.read_exact(&mut request).unwrap();
+        request
+    }
+
+    // answers an ApiVersions request, echoing its correlation id
+    fn answer_request(stream: &mut TcpStream) {
+        let request = read_request(stream);
+        stream.write_all(&[&[0, 0, 0, 10], &request[4..8], &[0, 0, 0, 0, 0, 0][..]].concat()).unwrap();
+    }
+
+    #[test]
+    fn verify_connection_reuse() {
+        // a broker accepting a single connection, and answering two ApiVersions requests on it
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let broker = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            for _ in 0..2 {
+                answer_request(&mut stream);
+            }
+        });
+
+        let api_client = ApiClient::new();
+        let server_addr = KafkaServerAddr::of(String::from("127.0.0.1"), port as i32, false);
+        for _ in 0..2 {
+            let response: Response<ApiVersionResponse> = api_client.request(&server_addr, Request::of(ApiVersionsRequest {})).unwrap();
+            assert_eq!(0, response.response_message.error_code);
+        }
+        broker.join().unwrap();
+    }
+
+    #[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+    #[test]
+    fn verify_tls_without_backend() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server_addr = KafkaServerAddr::of(String::from("127.0.0.1"), listener.local_addr().unwrap().port() as i32, true);
+        let result: Result<Response<ApiVersionResponse>, ApiRequestError> =
+            ApiClient::new().request(&server_addr, Request::of(ApiVersionsRequest {}));
+        assert!(result.unwrap_err().error.contains("'native-tls' or 'rustls' feature"));
+    }
+
+    #[test]
+    fn verify_reconnect_after_broker_closes_connection() {
+        // a broker closing its connection once the client has checked it in, without the client being told
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (checked_in, checked_in_receiver) = mpsc::channel();
+        let (closed_sender, closed) = mpsc::channel();
+        let broker = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            answer_request(&mut stream);
+            checked_in_receiver.recv().unwrap();
+            drop(stream);
+            closed_sender.send(()).unwrap();
+            let (mut stream, _) = listener.accept().unwrap();
+            answer_request(&mut stream);
+        });
+
+        let api_client = ApiClient::new();
+        let server_addr = KafkaServerAddr::of(String::from("127.0.0.1"), port as i32, false);
+        for request in 0..2 {
+            let response: Response<ApiVersionResponse> = api_client.request(&server_addr, Request::of(ApiVersionsRequest {})).unwrap();
+            assert_eq!(0, response.response_message.error_code);
+            if request == 0 {
+                checked_in.send(()).unwrap();
+                closed.recv().unwrap();
+            }
+        }
+        broker.join().unwrap();
+    }
 
-        let response = request_bytes.and_then(|bytes| match server_addr.use_tls {
-            false => tcp_stream(server_addr).and_then(|ref mut stream| write_bytes(stream, bytes).and_then(|_| read_bytes(stream))),
-            true => tcp_stream(server_addr).and_then(|stream| {
-                TlsConnector::new()
-                    .map_err(|err| ApiRequestError::of(err.to_string()))
-                    .and_then(|tls_connector| {
-                        tls_connector
-                            .connect(server_addr.domain.as_str(), stream)
-                            .map_err(|err| ApiRequestError::of(format!("TLS handshake error. {}", err)))
-                    })
-                    .and_then(|ref mut stream| write_bytes(stream, bytes).and_then(|_| read_bytes(stream)))
-            }),
+    #[test]
+    fn verify_no_resend_after_connection_fails_unanswered() {
+        // a broker closing its connection upon a request, which it may already have handled
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let broker = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            answer_request(&mut stream);
+            read_request(&mut stream);
+            drop(stream);
+            listener
         });
 
-        // DEBUG response
-        //        response.iter().for_each(|bytes| {
-        //            println!("bytes for API key {}:v{}: {:?}", _api_key, _api_key_version, ::to_hex_array(&bytes));
-        //        });
+        let api_client = ApiClient::new();
+        let server_addr = KafkaServerAddr::of(String::from("127.0.0.1"), port as i32, false);
+        let response: Response<ApiVersionResponse> = api_client.request(&server_addr, Request::of(ApiVersionsRequest {})).unwrap();
+        assert_eq!(0, response.response_message.error_code);
+        let result: Result<Response<ApiVersionResponse>, ApiRequestError> = api_client.request(&server_addr, Request::of(ApiVersionsRequest {}));
+        assert_eq!(ApiRequestErrorKind::Other, result.unwrap_err().kind);
 
-        response.and_then(|bytes| bytes.into_protocol_type().map_err(|e| ApiRequestError::of(e.error)))
+        // the request was not sent again on another connection
+        let listener = broker.join().unwrap();
+        listener.set_nonblocking(true).unwrap();
+        assert_eq!(ErrorKind::WouldBlock, listener.accept().unwrap_err().kind());
     }
 }
@@ -0,0 +1,57 @@
+use std::collections::BTreeMap;
+
+use crate::api_client::ApiRequestError;
+
+// separates the key/value pairs of the client initial response (RFC 7628)
+const SEPARATOR: char = '\u{1}';
+
+// the key of the bearer token, which extensions cannot use
+const AUTH_KEY: &str = "auth";
+
+/// The OAUTHBEARER client initial response: the GS2 header, without an
+/// authorization identity, followed by the bearer token and any extensions.
+///
+pub(crate) fn client_initial_response(token: &str, extensions: &BTreeMap<String, String>) -> Result<Vec<u8>, ApiRequestError> {
+    let extensions = extensions
+        .iter()
+        .map(|(key, value)| validate_extension(key, value).map(|_| format!("{}={}{}", key, value, SEPARATOR)))
+        .collect::<Result<Vec<String>, ApiRequestError>>()?;
+    Ok(format!("n,,{}{}=Bearer {}{}{}{}", SEPARATOR, AUTH_KEY, token, SEPARATOR, extensions.concat(), SEPARATOR).into_bytes())
+}
+
+/// The response to the broker's error challenge, after which it fails the
+/// authentication
+pub(crate) fn error_challenge_response() -> Vec<u8> {
+    vec![SEPARATOR as u8]
+}
+
+fn validate_extension(key: &str, value: &str) -> Result<(), ApiRequestError> {
+    if key == AUTH_KEY || key.is_empty() || !key.chars().all(|c| c.is_ascii_alphabetic()) {
+        Err(ApiRequestError::of(format!("Invalid OAUTHBEARER extension key '{}'", key)))
+    } else if !value.chars().all(|c| c == ' ' || c == '\t' || c == '\r' || c == '\n' || ('\u{21}'..='\u{7E}').contains(&c)) {
+        Err(ApiRequestError::of(format!("Invalid value for OAUTHBEARER extension {}", key)))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_client_initial_response() {
+        let extensions = vec![(String::from("logicalCluster"), String::from("lkc-1")), (String::from("a"), String::from("b"))].into_iter().collect();
+        let response = client_initial_response("t0k3n", &extensions).unwrap();
+        assert_eq!("n,,\u{1}auth=Bearer t0k3n\u{1}a=b\u{1}logicalCluster=lkc-1\u{1}\u{1}", String::from_utf8(response).unwrap());
+        assert_eq!(b"n,,\x01auth=Bearer t0k3n\x01\x01".to_vec(), client_initial_response("t0k3n", &BTreeMap::new()).unwrap());
+    }
+
+    #[test]
+    fn verify_invalid_extensions() {
+        let reserved = vec![(String::from("auth"), String::from("x"))].into_iter().collect();
+        assert!(client_initial_response("t", &reserved).is_err());
+        let separator = vec![(String::from("a"), String::from("\u{1}"))].into_iter().collect();
+        assert!(client_initial_response("t", &separator).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use crate::api_client::connection_pool::Connection;
+use crate::api_client::oauthbearer;
+use crate::api_client::scram::{ScramClient, ScramHash};
+use crate::api_client::{send, ApiRequestError};
+use crate::kafka_protocol::protocol_request::Request;
+use crate::kafka_protocol::protocol_requests::saslauthenticate_request::SaslAuthenticateRequest;
+use crate::kafka_protocol::protocol_requests::saslhandshake_request::SaslHandshakeRequest;
+use crate::kafka_protocol::protocol_response::Response;
+use crate::kafka_protocol::protocol_responses::saslauthenticate_response::SaslAuthenticateResponse;
+use crate::kafka_protocol::protocol_responses::saslhandshake_response::SaslHandshakeResponse;
+
+const UNSUPPORTED_SASL_MECHANISM: i16 = 33;
+
+/// The SASL mechanism, and its credentials, used to authenticate each
+/// connection to a broker before any other request is sent.
+///
+#[derive(Clone)]
+pub enum SaslMechanism {
+    Plain {
+        username: String,
+        password: String,
+    },
+    ScramSha256 {
+        username: String,
+        password: String,
+    },
+    ScramSha512 {
+        username: String,
+        password: String,
+    },
+    /// with SASL extensions (e.g. logicalCluster) sent along with each token
+    OAuthBearer {
+        token_provider: Arc<dyn OAuthBearerTokenProvider>,
+        extensions: BTreeMap<String, String>,
+    },
+}
+
+/// Provides the OAuth bearer token for each new connection, so that an
+/// implementation can refresh the token before it expires.
+///
+pub trait OAuthBearerTokenProvider: Send + Sync {
+    fn token(&self) -> Result<String, ApiRequestError>;
+}
+
+impl SaslMechanism {
+    pub fn plain(username: &str, password: &str) -> SaslMechanism {
+        SaslMechanism::Plain { username: String::from(username), password: String::from(password) }
+    }
+
+    pub fn scram_sha_256(username: &str, password: &str) -> SaslMechanism {
+        SaslMechanism::ScramSha256 { username: String::from(username), password: String::from(password) }
+    }
+
+    pub fn scram_sha_512(username: &str, password: &str) -> SaslMechanism {
+        SaslMechanism::ScramSha512 { username: String::from(username), password: String::from(password) }
+    }
+
+    pub fn oauth_bearer(token_provider: Arc<dyn OAuthBearerTokenProvider>, extensions: BTreeMap<String, String>) -> SaslMechanism {
+        SaslMechanism::OAuthBearer { token_provider, extensions }
+    }
+
+    /// The mechanism's name, as enabled on the broker (sasl.enabled.mechanisms)
+    pub fn name(&self) -> &str {
+        match self {
+            SaslMechanism::Plain { .. } => "PLAIN",
+            SaslMechanism::ScramSha256 { .. } => "SCRAM-SHA-256",
+            SaslMechanism::ScramSha512 { .. } => "SCRAM-SHA-512",
+            SaslMechanism::OAuthBearer { .. } => "OAUTHBEARER",
+        }
+    }
+}
+
+// credentials are left out so that they aren't logged along with a KafkaServerAddr
+impl Debug for SaslMechanism {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SaslMechanism::Plain { username, .. } => write!(f, "Plain {{ username: {:?} }}", username),
+            SaslMechanism::ScramSha256 { username, .. } => write!(f, "ScramSha256 {{ username: {:?} }}", username),
+            SaslMechanism::ScramSha512 { username, .. } => write!(f, "ScramSha512 {{ username: {:?} }}", username),
+            SaslMechanism::OAuthBearer { extensions, .. } => write!(f, "OAuthBearer {{ extensions: {:?} }}", extensions),
+        }
+    }
+}
+
+/// Authenticates a newly established connection with SaslHandshake, followed
+/// by the SaslAuthenticate exchange(s) of the mechanism.
+///
+pub(crate) fn authenticate<S: Read + Write>(connection: &mut Connection<S>, mechanism: &SaslMechanism) -> Result<(), ApiRequestError> {
+    let response: Response<SaslHandshakeResponse> =
+        send(connection, Request::of(SaslHandshakeRequest { mechanism: String::from(mechanism.name()) }))?;
+    let SaslHandshakeResponse { error_code, mechanisms } = response.response_message;
+    match error_code {
+        0 => Ok(()),
+        UNSUPPORTED_SASL_MECHANISM => Err(ApiRequestError::of(format!(
+            "SASL mechanism {} is not enabled on the broker, which supports {}",
+            mechanism.name(),
+            mechanisms.join(", ")
+        ))),
+        error_code => Err(ApiRequestError::of(format!("SaslHandshake failed with error code {}", error_code))),
+    }?;
+
+    match mechanism {
+        SaslMechanism::Plain { username, password } => sasl_authenticate(connection, plain_auth_bytes(username, password)).map(|_| ()),
+        SaslMechanism::ScramSha256 { username, password } => scram_authenticate(connection, ScramClient::new(ScramHash::Sha256, username, password)?),
+        SaslMechanism::ScramSha512 { username, password } => scram_authenticate(connection, ScramClient::new(ScramHash::Sha512, username, password)?),
+        SaslMechanism::OAuthBearer { token_provider, extensions } => oauth_bearer_authenticate(connection, token_provider.as_ref(), extensions),
+    }
+}
+
+/// A rejected token is answered with an error challenge (JSON describing the
+/// failure) rather than an error code, which fails the authentication once
+/// acknowledged.
+///
+fn oauth_bearer_authenticate<S: Read + Write>(
+    connection: &mut Connection<S>,
+    token_provider: &dyn OAuthBearerTokenProvider,
+    extensions: &BTreeMap<String, String>,
+) -> Result<(), ApiRequestError> {
+    let initial_response = token_provider.token().and_then(|token| oauthbearer::client_initial_response(&token, extensions))?;
+    let challenge = sasl_authenticate(connection, initial_response)?;
+    if challenge.is_empty() {
+        Ok(())
+    } else {
+        let challenge = String::from_utf8_lossy(&challenge).into_owned();
+        match sasl_authenticate(connection, oauthbearer::error_challenge_response()) {
+            Ok(_) => Err(ApiRequestError::of(format!("OAUTHBEARER authentication failed: {}", challenge))),
+            Err(err) => Err(ApiRequestError::of(format!("{} ({})", err.error, challenge))),
+        }
+    }
+}
+
+/// The client-first, server-first, client-final and server-final messages
+/// of SCRAM, each exchanged through SaslAuthenticate
+fn scram_authenticate<S: Read + Write>(connection: &mut Connection<S>, scram_client: ScramClient) -> Result<(), ApiRequestError> {
+    let server_first = sasl_authenticate(connection, scram_client.client_first_message().into_bytes()).and_then(utf8)?;
+    let client_final = scram_client.client_final_message(&server_first)?;
+    let server_final = sasl_authenticate(connection, client_final.message.clone().into_bytes()).and_then(utf8)?;
+    scram_client.verify_server_final(&client_final, &server_final)
+}
+
+fn utf8(bytes: Vec<u8>) -> Result<String, ApiRequestError> {
+    String::from_utf8(bytes).map_err(|err| ApiRequestError::of(format!("Invalid SASL message from the broker. {}", err)))
+}
+
+/// Sends the client's authentication bytes, returning those of the broker
+fn sasl_authenticate<S: Read + Write>(connection: &mut Connection<S>, auth_bytes: Vec<u8>) -> Result<Vec<u8>, ApiRequestError> {
+    let response: Response<SaslAuthenticateResponse> = send(connection, Request::of(SaslAuthenticateRequest { auth_bytes }))?;
+    let SaslAuthenticateResponse { error_code, error_message, auth_bytes, .. } = response.response_message;
+    match error_code {
+        0 => Ok(auth_bytes),
+        error_code => {
+            Err(ApiRequestError::of(format!("SASL authentication failed with error code {}: {}", error_code, error_message.unwrap_or_default())))
+        }
+    }
+}
+
+// RFC 4616: an empty authorization identity, so the broker authorizes as the username
+fn plain_auth_bytes(username: &str, password: &str) -> Vec<u8> {
+    [&[0], username.as_bytes(), &[0], password.as_bytes()].concat()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::api_client::tests::MockStream;
+
+    use super::*;
+
+    pub(crate) fn handshake_response(error_code: i16, mechanisms: &[&str]) -> Vec<u8> {
+        let mechanisms = mechanisms.iter().map(|mechanism| [(mechanism.len() as i16).to_be_bytes().to_vec(), mechanism.as_bytes().to_vec()].concat());
+        [error_code.to_be_bytes().to_vec(), (mechanisms.len() as i32).to_be_bytes().to_vec(), mechanisms.collect::<Vec<Vec<u8>>>().concat()].concat()
+    }
+
+    pub(crate) fn authenticate_response(error_code: i16, error_message: Option<&str>, auth_bytes: &[u8]) -> Vec<u8> {
+        let error_message = error_message
+            .map(|message| [(message.len() as i16).to_be_bytes().to_vec(), message.as_bytes().to_vec()].concat())
+            .unwrap_or_else(|| vec![255, 255]);
+        [error_code.to_be_bytes().to_vec(), error_message, (auth_bytes.len() as i32).to_be_bytes().to_vec(), auth_bytes.to_vec(), vec![0; 8]].concat()
+    }
+
+    #[test]
+    fn verify_plain_authentication() {
+        let mut connection = Connection::new(MockStream::of(vec![handshake_response(0, &["PLAIN"]), authenticate_response(0, None, &[])]));
+        authenticate(&mut connection, &SaslMechanism::plain("alice", "secret")).unwrap();
+
+        let output = &connection.stream().output;
+        let auth_bytes = [vec![0, 0, 0, 13], plain_auth_bytes("alice", "secret")].concat();
+        assert_eq!(&auth_bytes[..], &output[output.len() - auth_bytes.len()..]);
+        assert_eq!(vec![0, 17], output[4..6].to_vec());
+    }
+
+    #[test]
+    fn verify_failed_authentication() {
+        let mut connection =
+            Connection::new(MockStream::of(vec![handshake_response(0, &["PLAIN"]), authenticate_response(58, Some("Invalid credentials"), &[])]));
+        let error = authenticate(&mut connection, &SaslMechanism::plain("alice", "wrong")).unwrap_err();
+        assert_eq!("SASL authentication failed with error code 58: Invalid credentials", error.error);
+    }
+
+    #[test]
+    fn verify_unsupported_mechanism() {
+        let mut connection = Connection::new(MockStream::of(vec![handshake_response(UNSUPPORTED_SASL_MECHANISM, &["SCRAM-SHA-512", "GSSAPI"])]));
+        let error = authenticate(&mut connection, &SaslMechanism::plain("alice", "secret")).unwrap_err();
+        assert_eq!("SASL mechanism PLAIN is not enabled on the broker, which supports SCRAM-SHA-512, GSSAPI", error.error);
+    }
+
+    struct StaticTokenProvider(&'static str);
+
+    impl OAuthBearerTokenProvider for StaticTokenProvider {
+        fn token(&self) -> Result<String, ApiRequestError> {
+            Ok(String::from(self.0))
+        }
+    }
+
+    #[test]
+    fn verify_oauth_bearer_authentication() {
+        let mut connection = Connection::new(MockStream::of(vec![handshake_response(0, &["OAUTHBEARER"]), authenticate_response(0, None, &[])]));
+        let extensions = vec![(String::from("logicalCluster"), String::from("lkc-1"))].into_iter().collect();
+        authenticate(&mut connection, &SaslMechanism::oauth_bearer(Arc::new(StaticTokenProvider("t0k3n")), extensions)).unwrap();
+
+        let output = &connection.stream().output;
+        let auth_bytes = b"n,,\x01auth=Bearer t0k3n\x01logicalCluster=lkc-1\x01\x01";
+        assert_eq!(&auth_bytes[..], &output[output.len() - auth_bytes.len()..]);
+    }
+
+    #[test]
+    fn verify_oauth_bearer_error_challenge() {
+        let challenge = br#"{"status":"invalid_token"}"#;
+        let mut connection = Connection::new(MockStream::of(vec![
+            handshake_response(0, &["OAUTHBEARER"]),
+            authenticate_response(0, None, challenge),
+            authenticate_response(58, Some("Authentication failed"), &[]),
+        ]));
+        let error =
+            authenticate(&mut connection, &SaslMechanism::oauth_bearer(Arc::new(StaticTokenProvider("expired")), BTreeMap::new())).unwrap_err();
+        assert_eq!(r#"SASL authentication failed with error code 58: Authentication failed ({"status":"invalid_token"})"#, error.error);
+        let output = &connection.stream().output;
+        assert_eq!(vec![0, 0, 0, 1, 1], output[output.len() - 5..].to_vec());
+    }
+}
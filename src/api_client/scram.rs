@@ -0,0 +1,188 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::api_client::ApiRequestError;
+
+// the fewest iterations Kafka allows for SCRAM credentials
+const MIN_ITERATIONS: u32 = 4096;
+
+// base64 of the GS2 header "n,,", i.e. no channel binding or authorization identity
+const CHANNEL_BINDING: &str = "biws";
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ScramHash {
+    Sha256,
+    Sha512,
+}
+
+impl ScramHash {
+    fn hash(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            ScramHash::Sha256 => Sha256::digest(data).to_vec(),
+            ScramHash::Sha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+
+    fn hmac(self, key: &[u8], data: &[u8]) -> Vec<u8> {
+        // HMAC accepts keys of any length
+        match self {
+            ScramHash::Sha256 => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC key of any length");
+                mac.update(data);
+                mac.finalize().into_bytes().to_vec()
+            }
+            ScramHash::Sha512 => {
+                let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC key of any length");
+                mac.update(data);
+                mac.finalize().into_bytes().to_vec()
+            }
+        }
+    }
+
+    fn salted_password(self, password: &str, salt: &[u8], iterations: u32) -> Vec<u8> {
+        match self {
+            ScramHash::Sha256 => pbkdf2::pbkdf2_hmac_array::<Sha256, 32>(password.as_bytes(), salt, iterations).to_vec(),
+            ScramHash::Sha512 => pbkdf2::pbkdf2_hmac_array::<Sha512, 64>(password.as_bytes(), salt, iterations).to_vec(),
+        }
+    }
+}
+
+/// The client side of a SCRAM exchange (RFC 5802): the client-first message,
+/// the client-final message answering the server-first message, and the
+/// verification of the server-final message's signature.
+///
+pub(crate) struct ScramClient {
+    hash: ScramHash,
+    username: String,
+    password: String,
+    client_nonce: String,
+}
+
+/// The client-final message, and the server signature the server-final
+/// message must carry for the broker to be trusted
+#[derive(Debug)]
+pub(crate) struct ClientFinal {
+    pub message: String,
+    pub server_signature: Vec<u8>,
+}
+
+impl ScramClient {
+    pub fn new(hash: ScramHash, username: &str, password: &str) -> Result<ScramClient, ApiRequestError> {
+        let mut nonce = [0u8; 32];
+        getrandom::getrandom(&mut nonce).map_err(|err| ApiRequestError::of(format!("Could not generate a SCRAM nonce. {}", err)))?;
+        Ok(ScramClient::with_nonce(hash, username, password, &STANDARD.encode(nonce)))
+    }
+
+    fn with_nonce(hash: ScramHash, username: &str, password: &str, client_nonce: &str) -> ScramClient {
+        ScramClient { hash, username: String::from(username), password: String::from(password), client_nonce: String::from(client_nonce) }
+    }
+
+    fn client_first_bare(&self) -> String {
+        format!("n={},r={}", self.username.replace('=', "=3D").replace(',', "=2C"), self.client_nonce)
+    }
+
+    pub fn client_first_message(&self) -> String {
+        format!("n,,{}", self.client_first_bare())
+    }
+
+    pub fn client_final_message(&self, server_first: &str) -> Result<ClientFinal, ApiRequestError> {
+        let attributes = attributes(server_first);
+        if let Some(error) = attribute(&attributes, "e") {
+            return Err(ApiRequestError::of(format!("SCRAM authentication failed: {}", error)));
+        }
+
+        let nonce = attribute(&attributes, "r").ok_or_else(|| ApiRequestError::from("SCRAM server-first message has no nonce"))?;
+        if !nonce.starts_with(&self.client_nonce) || nonce.len() == self.client_nonce.len() {
+            return Err(ApiRequestError::from("SCRAM server nonce does not extend the client nonce"));
+        }
+        let salt = attribute(&attributes, "s")
+            .ok_or_else(|| ApiRequestError::from("SCRAM server-first message has no salt"))
+            .and_then(|salt| STANDARD.decode(salt).map_err(|err| ApiRequestError::of(format!("Invalid SCRAM salt. {}", err))))?;
+        let iterations = attribute(&attributes, "i")
+            .and_then(|iterations| iterations.parse::<u32>().ok())
+            .ok_or_else(|| ApiRequestError::from("SCRAM server-first message has no valid iteration count"))?;
+        if iterations < MIN_ITERATIONS {
+            return Err(ApiRequestError::of(format!("SCRAM iteration count {} is less than the minimum {}", iterations, MIN_ITERATIONS)));
+        }
+
+        let salted_password = self.hash.salted_password(&self.password, &salt, iterations);
+        let client_key = self.hash.hmac(&salted_password, b"Client Key");
+        let stored_key = self.hash.hash(&client_key);
+        let client_final_without_proof = format!("c={},r={}", CHANNEL_BINDING, nonce);
+        let auth_message = format!("{},{},{}", self.client_first_bare(), server_first, client_final_without_proof);
+        let client_signature = self.hash.hmac(&stored_key, auth_message.as_bytes());
+        let client_proof = client_key.iter().zip(client_signature).map(|(key, signature)| key ^ signature).collect::<Vec<u8>>();
+
+        let server_key = self.hash.hmac(&salted_password, b"Server Key");
+        Ok(ClientFinal {
+            message: format!("{},p={}", client_final_without_proof, STANDARD.encode(client_proof)),
+            server_signature: self.hash.hmac(&server_key, auth_message.as_bytes()),
+        })
+    }
+
+    pub fn verify_server_final(&self, client_final: &ClientFinal, server_final: &str) -> Result<(), ApiRequestError> {
+        let attributes = attributes(server_final);
+        match (attribute(&attributes, "e"), attribute(&attributes, "v")) {
+            (Some(error), _) => Err(ApiRequestError::of(format!("SCRAM authentication failed: {}", error))),
+            (None, Some(verifier)) if STANDARD.decode(verifier).ok().as_ref() == Some(&client_final.server_signature) => Ok(()),
+            (None, Some(_)) => {
+                Err(ApiRequestError::from("SCRAM server signature does not verify, so the broker could not prove it knows the user's credentials"))
+            }
+            (None, None) => Err(ApiRequestError::from("SCRAM server-final message has no server signature")),
+        }
+    }
+}
+
+// e.g. "r=nonce,s=salt,i=4096" as (name, value) pairs
+fn attributes(message: &str) -> Vec<(&str, &str)> {
+    message.split(',').filter_map(|attribute| attribute.split_once('=')).collect()
+}
+
+fn attribute<'a>(attributes: &[(&str, &'a str)], name: &str) -> Option<&'a str> {
+    attributes.iter().find(|(attribute_name, _)| *attribute_name == name).map(|(_, value)| *value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the SCRAM-SHA-256 example exchange of RFC 7677
+    const SERVER_FIRST: &str = "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+    const CLIENT_FINAL: &str = "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=";
+    const SERVER_FINAL: &str = "v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=";
+
+    fn client() -> ScramClient {
+        ScramClient::with_nonce(ScramHash::Sha256, "user", "pencil", "rOprNGfwEbeRWgbNEkqO")
+    }
+
+    #[test]
+    fn verify_scram_sha_256_exchange() {
+        let client = client();
+        assert_eq!("n,,n=user,r=rOprNGfwEbeRWgbNEkqO", client.client_first_message());
+        let client_final = client.client_final_message(SERVER_FIRST).unwrap();
+        assert_eq!(CLIENT_FINAL, client_final.message);
+        client.verify_server_final(&client_final, SERVER_FINAL).unwrap();
+    }
+
+    #[test]
+    fn verify_scram_server_signature_mismatch() {
+        let client = client();
+        let client_final = client.client_final_message(SERVER_FIRST).unwrap();
+        let error = client.verify_server_final(&client_final, "v=AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=").unwrap_err();
+        assert!(error.error.starts_with("SCRAM server signature does not verify"));
+    }
+
+    #[test]
+    fn verify_scram_server_nonce() {
+        let error = client().client_final_message("r=another,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096").unwrap_err();
+        assert_eq!("SCRAM server nonce does not extend the client nonce", error.error);
+    }
+
+    #[test]
+    fn verify_scram_username_escaping() {
+        let client = ScramClient::with_nonce(ScramHash::Sha512, "a=b,c", "p", "n");
+        assert_eq!("n,,n=a=3Db=2Cc,r=n", client.client_first_message());
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::api_client::ApiRequestError;
+
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+mod native_tls_backend;
+#[cfg(feature = "rustls")]
+mod rustls_backend;
+
+// rustls is used when its feature is enabled, even along with native-tls
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+use self::native_tls_backend as backend;
+#[cfg(feature = "rustls")]
+use self::rustls_backend as backend;
+
+// without either backend, only plaintext connections are possible
+#[cfg(any(feature = "native-tls", feature = "rustls"))]
+pub(crate) use self::backend::{connect, TlsStream};
+
+/// How TLS connections to brokers are established, when `use_tls` is set on
+/// a `KafkaServerAddr`, with either TLS backend. The default verifies the
+/// broker against the trusted root certificates, without a client certificate.
+///
+#[derive(Clone, Debug, Default)]
+pub struct TlsConfig {
+    /// a PEM file of CA certificates trusted in addition to the system's, e.g. a private CA
+    pub ca_certificates: Option<PathBuf>,
+    /// the certificate and key presented to brokers requiring mutual TLS
+    pub client_identity: Option<ClientIdentity>,
+    /// only for development clusters, e.g. with self-signed certificates
+    pub accept_invalid_certs: bool,
+    /// only for development clusters, e.g. brokers advertising an IP address
+    pub accept_invalid_hostnames: bool,
+    pub min_protocol_version: Option<TlsVersion>,
+}
+
+#[derive(Clone)]
+pub enum ClientIdentity {
+    /// a PEM certificate (chain) and its PKCS#8 PEM private key
+    Pem { certificate: PathBuf, key: PathBuf },
+    /// a PKCS#12 archive (e.g. a .p12 or .pfx file) and its password
+    Pkcs12 { archive: PathBuf, password: String },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TlsVersion {
+    Tlsv10,
+    Tlsv11,
+    Tlsv12,
+    Tlsv13,
+}
+
+// the password is left out so that it isn't logged along with a KafkaServerAddr
+impl Debug for ClientIdentity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ClientIdentity::Pem { certificate, key } => write!(f, "Pem {{ certificate: {:?}, key: {:?} }}", certificate, key),
+            ClientIdentity::Pkcs12 { archive, .. } => write!(f, "Pkcs12 {{ archive: {:?} }}", archive),
+        }
+    }
+}
+
+#[cfg_attr(not(any(feature = "native-tls", feature = "rustls")), allow(dead_code))]
+fn read_file(path: &Path) -> Result<Vec<u8>, ApiRequestError> {
+    fs::read(path).map_err(|err| ApiRequestError::of(format!("Could not read {}. {}", path.display(), err)))
+}
+
+#[cfg(all(test, any(feature = "native-tls", feature = "rustls")))]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+
+    #[test]
+    fn verify_default_tls_connector() {
+        assert!(backend::tls_connector(&TlsConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn verify_invalid_tls_files() {
+        let missing = TlsConfig { ca_certificates: Some(PathBuf::from("/nonexistent/ca.pem")), ..TlsConfig::default() };
+        let error = backend::tls_connector(&missing).unwrap_err();
+        assert!(error.error.starts_with("Could not read /nonexistent/ca.pem."));
+
+        // unique to this process, and removed before asserting so that a failure doesn't leave it behind
+        let not_pkcs12 = env::temp_dir().join(format!("topiks-not-pkcs12-{}.p12", process::id()));
+        fs::write(&not_pkcs12, b"not a PKCS#12 archive").unwrap();
+        let config = TlsConfig {
+            client_identity: Some(ClientIdentity::Pkcs12 { archive: not_pkcs12.clone(), password: String::from("secret") }),
+            ..TlsConfig::default()
+        };
+        let result = backend::tls_connector(&config);
+        fs::remove_file(&not_pkcs12).unwrap();
+        let error = result.unwrap_err();
+        assert!(error.error.starts_with(&format!("Invalid PKCS#12 archive {}.", not_pkcs12.display())));
+        assert!(!format!("{:?}", config).contains("secret"));
+    }
+}
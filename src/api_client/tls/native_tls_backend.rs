@@ -0,0 +1,60 @@
+use std::net::TcpStream;
+
+use native_tls::{Certificate, Identity, Protocol, TlsConnector};
+
+use crate::api_client::tls::{read_file, ClientIdentity, TlsConfig, TlsVersion};
+use crate::api_client::ApiRequestError;
+
+pub(crate) type TlsStream = native_tls::TlsStream<TcpStream>;
+
+impl From<TlsVersion> for Protocol {
+    fn from(version: TlsVersion) -> Self {
+        match version {
+            TlsVersion::Tlsv10 => Protocol::Tlsv10,
+            TlsVersion::Tlsv11 => Protocol::Tlsv11,
+            TlsVersion::Tlsv12 => Protocol::Tlsv12,
+            TlsVersion::Tlsv13 => Protocol::Tlsv13,
+        }
+    }
+}
+
+pub(crate) fn connect(tls_config: &TlsConfig, domain: &str, stream: TcpStream) -> Result<TlsStream, ApiRequestError> {
+    tls_connector(tls_config)
+        .and_then(|tls_connector| tls_connector.connect(domain, stream).map_err(|err| ApiRequestError::of(format!("TLS handshake error. {}", err))))
+}
+
+pub(super) fn tls_connector(tls_config: &TlsConfig) -> Result<TlsConnector, ApiRequestError> {
+    let mut builder = TlsConnector::builder();
+    builder
+        .danger_accept_invalid_certs(tls_config.accept_invalid_certs)
+        .danger_accept_invalid_hostnames(tls_config.accept_invalid_hostnames)
+        .min_protocol_version(tls_config.min_protocol_version.map(Protocol::from));
+
+    if let Some(ref ca_certificates) = tls_config.ca_certificates {
+        let certificates = read_file(ca_certificates).and_then(|pem| {
+            Certificate::stack_from_pem(&pem)
+                .map_err(|err| ApiRequestError::of(format!("Invalid CA certificates in {}. {}", ca_certificates.display(), err)))
+        })?;
+        for certificate in certificates {
+            builder.add_root_certificate(certificate);
+        }
+    }
+
+    if let Some(ref client_identity) = tls_config.client_identity {
+        let identity = match client_identity {
+            ClientIdentity::Pem { certificate, key } => read_file(certificate).and_then(|certificate_pem| {
+                read_file(key).and_then(|key_pem| {
+                    Identity::from_pkcs8(&certificate_pem, &key_pem)
+                        .map_err(|err| ApiRequestError::of(format!("Invalid client certificate or key in {}. {}", certificate.display(), err)))
+                })
+            }),
+            ClientIdentity::Pkcs12 { archive, password } => read_file(archive).and_then(|der| {
+                Identity::from_pkcs12(&der, password)
+                    .map_err(|err| ApiRequestError::of(format!("Invalid PKCS#12 archive {}. {}", archive.display(), err)))
+            }),
+        }?;
+        builder.identity(identity);
+    }
+
+    builder.build().map_err(|err| ApiRequestError::of(err.to_string()))
+}
@@ -0,0 +1,154 @@
+use std::convert::TryFrom;
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use p12_keystore::KeyStore;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::{ring, verify_tls12_signature, verify_tls13_signature, WebPkiSupportedAlgorithms};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName, UnixTime};
+use rustls::{
+    CertificateError, ClientConfig, ClientConnection, DigitallySignedStruct, Error, RootCertStore, SignatureScheme, StreamOwned,
+    SupportedProtocolVersion,
+};
+
+use crate::api_client::tls::{read_file, ClientIdentity, TlsConfig, TlsVersion};
+use crate::api_client::ApiRequestError;
+
+pub(crate) type TlsStream = StreamOwned<ClientConnection, TcpStream>;
+
+pub(crate) fn connect(tls_config: &TlsConfig, domain: &str, mut stream: TcpStream) -> Result<TlsStream, ApiRequestError> {
+    let client_config = tls_connector(tls_config)?;
+    let server_name =
+        ServerName::try_from(domain.to_owned()).map_err(|err| ApiRequestError::of(format!("Invalid TLS server name {}. {}", domain, err)))?;
+    let mut connection = ClientConnection::new(client_config, server_name).map_err(|err| ApiRequestError::of(err.to_string()))?;
+    while connection.is_handshaking() {
+        connection.complete_io(&mut stream).map_err(|err| ApiRequestError::of(format!("TLS handshake error. {}", err)))?;
+    }
+    Ok(StreamOwned::new(connection, stream))
+}
+
+/// Brokers are verified against the Mozilla root certificates (webpki-roots),
+/// rather than the system's. rustls only supports TLS 1.2 and 1.3, so lower
+/// minimum protocol versions are the same as TLS 1.2.
+///
+pub(super) fn tls_connector(tls_config: &TlsConfig) -> Result<Arc<ClientConfig>, ApiRequestError> {
+    let provider = Arc::new(ring::default_provider());
+    let versions: &[&SupportedProtocolVersion] = match tls_config.min_protocol_version {
+        Some(TlsVersion::Tlsv13) => &[&rustls::version::TLS13],
+        _ => rustls::ALL_VERSIONS,
+    };
+
+    let mut roots = RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    if let Some(ref ca_certificates) = tls_config.ca_certificates {
+        let invalid_certificates = |err: String| ApiRequestError::of(format!("Invalid CA certificates in {}. {}", ca_certificates.display(), err));
+        let certificates = read_file(ca_certificates).and_then(|pem| certificates(&pem).map_err(|err| invalid_certificates(err.error)))?;
+        for certificate in certificates {
+            roots.add(certificate).map_err(|err| invalid_certificates(err.to_string()))?;
+        }
+    }
+
+    let builder =
+        ClientConfig::builder_with_provider(provider.clone()).with_protocol_versions(versions).map_err(|err| ApiRequestError::of(err.to_string()))?;
+    let builder = if tls_config.accept_invalid_certs || tls_config.accept_invalid_hostnames {
+        let webpki_verifier = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+            .build()
+            .map_err(|err| ApiRequestError::of(err.to_string()))?;
+        builder.dangerous().with_custom_certificate_verifier(Arc::new(LenientServerCertVerifier {
+            webpki_verifier,
+            accept_invalid_certs: tls_config.accept_invalid_certs,
+            algorithms: provider.signature_verification_algorithms,
+        }))
+    } else {
+        builder.with_root_certificates(roots)
+    };
+
+    let client_config = match tls_config.client_identity {
+        Some(ClientIdentity::Pem { ref certificate, ref key }) => {
+            let invalid_identity =
+                |err: String| ApiRequestError::of(format!("Invalid client certificate or key in {}. {}", certificate.display(), err));
+            let certificates = read_file(certificate).and_then(|pem| certificates(&pem).map_err(|err| invalid_identity(err.error)))?;
+            let private_key = read_file(key).and_then(|pem| {
+                rustls_pemfile::private_key(&mut pem.as_slice())
+                    .map_err(|err| invalid_identity(err.to_string()))
+                    .and_then(|private_key| private_key.ok_or_else(|| invalid_identity(String::from("No private key found"))))
+            })?;
+            builder.with_client_auth_cert(certificates, private_key).map_err(|err| invalid_identity(err.to_string()))
+        }
+        Some(ClientIdentity::Pkcs12 { ref archive, ref password }) => {
+            let invalid_archive = |err: String| ApiRequestError::of(format!("Invalid PKCS#12 archive {}. {}", archive.display(), err));
+            let key_store =
+                read_file(archive).and_then(|der| KeyStore::from_pkcs12(&der, password).map_err(|err| invalid_archive(err.to_string())))?;
+            let (_, key_chain) = key_store.private_key_chain().ok_or_else(|| invalid_archive(String::from("No private key found")))?;
+            let certificates = key_chain.chain().iter().map(|certificate| CertificateDer::from(certificate.as_der().to_vec())).collect();
+            let private_key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_chain.key().to_vec()));
+            builder.with_client_auth_cert(certificates, private_key).map_err(|err| invalid_archive(err.to_string()))
+        }
+        None => Ok(builder.with_no_client_auth()),
+    }?;
+    Ok(Arc::new(client_config))
+}
+
+fn certificates(pem: &[u8]) -> Result<Vec<CertificateDer<'static>>, ApiRequestError> {
+    let mut pem = pem;
+    rustls_pemfile::certs(&mut pem).collect::<Result<Vec<CertificateDer<'static>>, _>>().map_err(|err| ApiRequestError::of(err.to_string())).and_then(
+        |certificates| match certificates.as_slice() {
+            [] => Err(ApiRequestError::from("No certificates found")),
+            _ => Ok(certificates),
+        },
+    )
+}
+
+/// Verifies brokers as webpki does, except for accepting any certificate or
+/// certificates for another hostname. Handshake signatures are still verified.
+///
+#[derive(Debug)]
+struct LenientServerCertVerifier {
+    webpki_verifier: Arc<WebPkiServerVerifier>,
+    accept_invalid_certs: bool,
+    algorithms: WebPkiSupportedAlgorithms,
+}
+
+impl ServerCertVerifier for LenientServerCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, Error> {
+        if self.accept_invalid_certs {
+            return Ok(ServerCertVerified::assertion());
+        }
+        match self.webpki_verifier.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now) {
+            Err(Error::InvalidCertificate(CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. })) => {
+                Ok(ServerCertVerified::assertion())
+            }
+            result => result,
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
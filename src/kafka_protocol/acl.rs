@@ -0,0 +1,264 @@
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// The types shared by the ACL APIs (DescribeAcls, CreateAcls and DeleteAcls).
+/// Values unknown to this client deserialize as `Unknown`.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AclResourceType {
+    Unknown = 0,
+    Any = 1,
+    Topic = 2,
+    Group = 3,
+    Cluster = 4,
+    TransactionalId = 5,
+    DelegationToken = 6,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AclPatternType {
+    Unknown = 0,
+    /// in filters, matches any pattern type
+    Any = 1,
+    /// in filters, matches the literal and prefixed patterns that would apply to a resource name
+    Match = 2,
+    Literal = 3,
+    Prefixed = 4,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AclOperation {
+    Unknown = 0,
+    Any = 1,
+    All = 2,
+    Read = 3,
+    Write = 4,
+    Create = 5,
+    Delete = 6,
+    Alter = 7,
+    Describe = 8,
+    ClusterAction = 9,
+    DescribeConfigs = 10,
+    AlterConfigs = 11,
+    IdempotentWrite = 12,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AclPermissionType {
+    Unknown = 0,
+    Any = 1,
+    Deny = 2,
+    Allow = 3,
+}
+
+impl From<i8> for AclResourceType {
+    fn from(value: i8) -> Self {
+        match value {
+            1 => AclResourceType::Any,
+            2 => AclResourceType::Topic,
+            3 => AclResourceType::Group,
+            4 => AclResourceType::Cluster,
+            5 => AclResourceType::TransactionalId,
+            6 => AclResourceType::DelegationToken,
+            _ => AclResourceType::Unknown,
+        }
+    }
+}
+
+impl From<i8> for AclPatternType {
+    fn from(value: i8) -> Self {
+        match value {
+            1 => AclPatternType::Any,
+            2 => AclPatternType::Match,
+            3 => AclPatternType::Literal,
+            4 => AclPatternType::Prefixed,
+            _ => AclPatternType::Unknown,
+        }
+    }
+}
+
+impl From<i8> for AclOperation {
+    fn from(value: i8) -> Self {
+        match value {
+            1 => AclOperation::Any,
+            2 => AclOperation::All,
+            3 => AclOperation::Read,
+            4 => AclOperation::Write,
+            5 => AclOperation::Create,
+            6 => AclOperation::Delete,
+            7 => AclOperation::Alter,
+            8 => AclOperation::Describe,
+            9 => AclOperation::ClusterAction,
+            10 => AclOperation::DescribeConfigs,
+            11 => AclOperation::AlterConfigs,
+            12 => AclOperation::IdempotentWrite,
+            _ => AclOperation::Unknown,
+        }
+    }
+}
+
+impl From<i8> for AclPermissionType {
+    fn from(value: i8) -> Self {
+        match value {
+            1 => AclPermissionType::Any,
+            2 => AclPermissionType::Deny,
+            3 => AclPermissionType::Allow,
+            _ => AclPermissionType::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourcePattern {
+    pub resource_type: AclResourceType,
+    pub resource_name: String,
+    pub pattern_type: AclPatternType,
+}
+
+/// e.g. principal "User:alice" allowed to Read from host "*"
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessControlEntry {
+    pub principal: String,
+    pub host: String,
+    pub operation: AclOperation,
+    pub permission_type: AclPermissionType,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AclBinding {
+    pub pattern: ResourcePattern,
+    pub entry: AccessControlEntry,
+}
+
+/// Matches ACL bindings, with None matching any name, principal or host, and
+/// the `Any` variants matching any type, operation or permission.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct AclBindingFilter {
+    pub resource_type: AclResourceType,
+    pub resource_name: Option<String>,
+    pub pattern_type: AclPatternType,
+    pub principal: Option<String>,
+    pub host: Option<String>,
+    pub operation: AclOperation,
+    pub permission_type: AclPermissionType,
+}
+
+impl AclBindingFilter {
+    pub fn any() -> AclBindingFilter {
+        AclBindingFilter {
+            resource_type: AclResourceType::Any,
+            resource_name: None,
+            pattern_type: AclPatternType::Any,
+            principal: None,
+            host: None,
+            operation: AclOperation::Any,
+            permission_type: AclPermissionType::Any,
+        }
+    }
+}
+
+impl ProtocolSerializable for AclBinding {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let AclBinding { pattern, entry } = self;
+        AclBindingFilter {
+            resource_type: pattern.resource_type,
+            resource_name: Some(pattern.resource_name),
+            pattern_type: pattern.pattern_type,
+            principal: Some(entry.principal),
+            host: Some(entry.host),
+            operation: entry.operation,
+            permission_type: entry.permission_type,
+        }
+        .into_protocol_bytes()
+    }
+}
+
+impl ProtocolSerializable for AclBindingFilter {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let resource_type = self.resource_type;
+        let resource_name = self.resource_name;
+        let pattern_type = self.pattern_type;
+        let principal = self.principal;
+        let host = self.host;
+        let operation = self.operation;
+        let permission_type = self.permission_type;
+        I8(resource_type as i8).into_protocol_bytes().and_then(|resource_type| {
+            resource_name.into_protocol_bytes().and_then(|resource_name| {
+                I8(pattern_type as i8).into_protocol_bytes().and_then(|pattern_type| {
+                    principal.into_protocol_bytes().and_then(|principal| {
+                        host.into_protocol_bytes().and_then(|host| {
+                            I8(operation as i8).into_protocol_bytes().and_then(|operation| {
+                                I8(permission_type as i8).into_protocol_bytes().map(|permission_type| {
+                                    [resource_type, resource_name, pattern_type, principal, host, operation, permission_type].concat()
+                                })
+                            })
+                        })
+                    })
+                })
+            })
+        })
+    }
+}
+
+pub(crate) fn de_resource_pattern(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, ResourcePattern>> {
+    let resource_type = AclResourceType::from(bytes[0] as i8);
+    de_string(&bytes[1..]).and_then(|(resource_name, bytes)| {
+        resource_name
+            .ok_or_else(|| DeserializeError::of("Expected resource_name"))
+            .map(|resource_name| (ResourcePattern { resource_type, resource_name, pattern_type: AclPatternType::from(bytes[0] as i8) }, &bytes[1..]))
+    })
+}
+
+pub(crate) fn de_access_control_entry(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, AccessControlEntry>> {
+    de_string(bytes).and_then(|(principal, bytes)| {
+        de_string(bytes).and_then(|(host, bytes)| match (principal, host) {
+            (Some(principal), Some(host)) => Ok((
+                AccessControlEntry {
+                    principal,
+                    host,
+                    operation: AclOperation::from(bytes[0] as i8),
+                    permission_type: AclPermissionType::from(bytes[1] as i8),
+                },
+                &bytes[2..],
+            )),
+            _ => Err(DeserializeError::of("Expected principal and host")),
+        })
+    })
+}
+
+pub(crate) fn de_acl_binding(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, AclBinding>> {
+    de_resource_pattern(bytes)
+        .and_then(|(pattern, bytes)| de_access_control_entry(bytes).map(|(entry, bytes)| (AclBinding { pattern, entry }, bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_serde_for_acl_binding() {
+        let binding = AclBinding {
+            pattern: ResourcePattern {
+                resource_type: AclResourceType::Topic,
+                resource_name: String::from("t"),
+                pattern_type: AclPatternType::Prefixed,
+            },
+            entry: AccessControlEntry {
+                principal: String::from("User:a"),
+                host: String::from("*"),
+                operation: AclOperation::Read,
+                permission_type: AclPermissionType::Allow,
+            },
+        };
+        let bytes = binding.clone().into_protocol_bytes().unwrap();
+        assert_eq!(vec![2, 0, 1, 116, 4, 0, 6, 85, 115, 101, 114, 58, 97, 0, 1, 42, 3, 3], bytes);
+        assert_eq!((binding, &[][..]), de_acl_binding(&bytes).unwrap());
+    }
+
+    #[test]
+    fn verify_unknown_acl_values() {
+        assert_eq!(AclOperation::Unknown, AclOperation::from(42));
+        assert_eq!(AclResourceType::Unknown, AclResourceType::from(-1));
+    }
+}
@@ -5,13 +5,24 @@ use crate::kafka_protocol::api_verification::ApiVerificationFailure::ApiVersionN
 use crate::kafka_protocol::api_verification::ApiVerificationFailure::NoVerification;
 use crate::kafka_protocol::protocol_request::Request;
 use crate::kafka_protocol::protocol_requests::alterconfigs_request::AlterConfigsRequest;
+use crate::kafka_protocol::protocol_requests::createacls_request::CreateAclsRequest;
+use crate::kafka_protocol::protocol_requests::createpartitions_request::CreatePartitionsRequest;
 use crate::kafka_protocol::protocol_requests::createtopics_request::CreateTopicsRequest;
+use crate::kafka_protocol::protocol_requests::deleteacls_request::DeleteAclsRequest;
+use crate::kafka_protocol::protocol_requests::deletegroups_request::DeleteGroupsRequest;
+use crate::kafka_protocol::protocol_requests::deleterecords_request::DeleteRecordsRequest;
 use crate::kafka_protocol::protocol_requests::deletetopics_request::DeleteTopicsRequest;
+use crate::kafka_protocol::protocol_requests::describeacls_request::DescribeAclsRequest;
 use crate::kafka_protocol::protocol_requests::describeconfigs_request::DescribeConfigsRequest;
+use crate::kafka_protocol::protocol_requests::describegroups_request::LegacyDescribeGroupsRequest;
+use crate::kafka_protocol::protocol_requests::describelogdirs_request::DescribeLogDirsRequest;
+use crate::kafka_protocol::protocol_requests::fetch_request::FetchRequest;
 use crate::kafka_protocol::protocol_requests::findcoordinator_request::FindCoordinatorRequest;
 use crate::kafka_protocol::protocol_requests::listoffsets_request::ListOffsetsRequest;
 use crate::kafka_protocol::protocol_requests::metadata_request::MetadataRequest;
+use crate::kafka_protocol::protocol_requests::offsetcommit_request::OffsetCommitRequest;
 use crate::kafka_protocol::protocol_requests::offsetfetch_request::OffsetFetchRequest;
+use crate::kafka_protocol::protocol_requests::produce_request::ProduceRequest;
 use crate::kafka_protocol::protocol_response::Response;
 use crate::kafka_protocol::protocol_serializable::*;
 use crate::KafkaServerAddr;
@@ -56,7 +67,7 @@ pub struct ApiVersion {
 
 impl ProtocolDeserializable<ApiVersionResponse> for Vec<u8> {
     fn into_protocol_type(self) -> ProtocolDeserializeResult<ApiVersionResponse> {
-        fn deserialize_api_version(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<ApiVersion>> {
+        fn deserialize_api_version(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, ApiVersion>> {
             de_i16(&bytes[0..=1]).and_then(|api_key| {
                 de_i16(&bytes[2..=3])
                     .and_then(|min_version| de_i16(&bytes[4..=5]).map(|max_version| (ApiVersion { api_key, min_version, max_version }, &bytes[6..])))
@@ -71,10 +82,25 @@ impl ProtocolDeserializable<ApiVersionResponse> for Vec<u8> {
 
 pub struct ApiVersionQuery(pub i16, pub i16); // api -> version
 
+/// Whether a broker supports a version of an API, for optional APIs which
+/// are not in `apis_in_use` as older brokers lack them, e.g. those preferred
+/// over older APIs when available.
+///
+pub fn is_supported<T: ApiClientTrait>(api_client: &T, server_addr: &KafkaServerAddr, query: &ApiVersionQuery) -> Result<bool, ApiRequestError> {
+    let result: Result<Response<ApiVersionResponse>, ApiRequestError> = api_client.request(server_addr, Request::of(ApiVersionsRequest {}));
+    result.map(|response| {
+        response
+            .response_message
+            .api_versions
+            .iter()
+            .any(|version| version.api_key == query.0 && query.1 >= version.min_version && query.1 <= version.max_version)
+    })
+}
+
 pub fn apply<T: ApiClientTrait + 'static>(
     api_client: T,
     server_addr: &KafkaServerAddr,
-    queries: &Vec<ApiVersionQuery>,
+    queries: &[ApiVersionQuery],
 ) -> Result<(), Vec<ApiVerificationFailure>> {
     let result: Result<Response<ApiVersionResponse>, ApiRequestError> = api_client.request(server_addr, Request::of(ApiVersionsRequest {}));
 
@@ -115,17 +141,32 @@ pub fn apply<T: ApiClientTrait + 'static>(
 pub trait KafkaApiVersioned {
     fn api_key() -> i16;
     fn version() -> i16;
+    /// whether this version uses compact types and tagged fields
+    fn flexible() -> bool {
+        false
+    }
 }
 
 pub fn apis_in_use() -> Vec<ApiVersionQuery> {
     vec![
         ApiVersionQuery(AlterConfigsRequest::api_key(), AlterConfigsRequest::version()),
+        ApiVersionQuery(CreateAclsRequest::api_key(), CreateAclsRequest::version()),
+        ApiVersionQuery(CreatePartitionsRequest::api_key(), CreatePartitionsRequest::version()),
         ApiVersionQuery(CreateTopicsRequest::api_key(), CreateTopicsRequest::version()),
+        ApiVersionQuery(DeleteAclsRequest::api_key(), DeleteAclsRequest::version()),
+        ApiVersionQuery(DeleteGroupsRequest::api_key(), DeleteGroupsRequest::version()),
+        ApiVersionQuery(DeleteRecordsRequest::api_key(), DeleteRecordsRequest::version()),
         ApiVersionQuery(DeleteTopicsRequest::api_key(), DeleteTopicsRequest::version()),
+        ApiVersionQuery(DescribeAclsRequest::api_key(), DescribeAclsRequest::version()),
         ApiVersionQuery(DescribeConfigsRequest::api_key(), DescribeConfigsRequest::version()),
+        ApiVersionQuery(LegacyDescribeGroupsRequest::api_key(), LegacyDescribeGroupsRequest::version()),
+        ApiVersionQuery(DescribeLogDirsRequest::api_key(), DescribeLogDirsRequest::version()),
+        ApiVersionQuery(FetchRequest::api_key(), FetchRequest::version()),
         ApiVersionQuery(FindCoordinatorRequest::api_key(), FindCoordinatorRequest::version()),
         ApiVersionQuery(ListOffsetsRequest::api_key(), ListOffsetsRequest::version()),
         ApiVersionQuery(MetadataRequest::api_key(), MetadataRequest::version()),
+        ApiVersionQuery(OffsetCommitRequest::api_key(), OffsetCommitRequest::version()),
         ApiVersionQuery(OffsetFetchRequest::api_key(), OffsetFetchRequest::version()),
+        ApiVersionQuery(ProduceRequest::api_key(), ProduceRequest::version()),
     ]
 }
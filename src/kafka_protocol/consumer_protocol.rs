@@ -0,0 +1,136 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// The embedded protocol used by consumer groups within the
+/// opaque member metadata and assignment of the group APIs.
+///
+pub const CONSUMER_PROTOCOL_TYPE: &str = "consumer";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsumerMember {
+    pub member_id: String,
+    pub client_id: String,
+    pub client_host: String,
+    pub subscription: ConsumerSubscription,
+    pub assignment: ConsumerAssignment,
+}
+
+/// Member metadata. Fields added by versions newer than 1 are ignored.
+/// Empty when a group is rebalancing, as members rejoin.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConsumerSubscription {
+    pub version: i16,
+    pub topics: Vec<String>,
+    pub user_data: Option<Vec<u8>>,
+    pub owned_partitions: Vec<TopicPartitions>,
+}
+
+/// Member assignment. Empty when a member has not been assigned yet.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConsumerAssignment {
+    pub version: i16,
+    pub assigned_partitions: Vec<TopicPartitions>,
+    pub user_data: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopicPartitions {
+    pub topic: String,
+    pub partitions: Vec<i32>,
+}
+
+impl ProtocolDeserializable<ConsumerSubscription> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<ConsumerSubscription> {
+        if self.is_empty() {
+            return Ok(ConsumerSubscription::default());
+        }
+        de_version(&self).and_then(|(version, bytes)| {
+            de_array(bytes, deserialize_topic).and_then(|(topics, bytes)| {
+                de_bytes(bytes).and_then(|(user_data, bytes)| {
+                    let owned_partitions = match version {
+                        0 => Ok(vec![]),
+                        _ => de_array(bytes, deserialize_topic_partitions).map(|(owned_partitions, _bytes)| owned_partitions),
+                    };
+                    owned_partitions.map(|owned_partitions| ConsumerSubscription { version, topics, user_data, owned_partitions })
+                })
+            })
+        })
+    }
+}
+
+impl ProtocolDeserializable<ConsumerAssignment> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<ConsumerAssignment> {
+        if self.is_empty() {
+            return Ok(ConsumerAssignment::default());
+        }
+        de_version(&self).and_then(|(version, bytes)| {
+            de_array(bytes, deserialize_topic_partitions).and_then(|(assigned_partitions, bytes)| {
+                de_bytes(bytes).map(|(user_data, _bytes)| ConsumerAssignment { version, assigned_partitions, user_data })
+            })
+        })
+    }
+}
+
+fn de_version(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, i16>> {
+    bytes.get(0..2).ok_or_else(|| DeserializeError::of("Consumer protocol bytes too small for version")).and_then(de_i16).and_then(|version| {
+        if version < 0 {
+            Err(DeserializeError::of(&format!("Unexpected consumer protocol version {}", version)))
+        } else {
+            Ok((version, &bytes[2..]))
+        }
+    })
+}
+
+fn deserialize_topic(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, String>> {
+    de_string(bytes).and_then(|(topic, bytes)| topic.ok_or_else(|| DeserializeError::of("Expected topic name")).map(|topic| (topic, bytes)))
+}
+
+fn deserialize_topic_partitions(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, TopicPartitions>> {
+    deserialize_topic(bytes).and_then(|(topic, bytes)| {
+        de_array(bytes, |bytes| de_i32(&bytes[0..4]).map(|partition| (partition, &bytes[4..])))
+            .map(|(partitions, bytes)| (TopicPartitions { topic, partitions }, bytes))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+    use crate::kafka_protocol::protocol_serializable::ProtocolSerializable;
+
+    use super::*;
+
+    #[test]
+    fn verify_consumer_subscription_v1() {
+        let bytes = [
+            I16(1).into_protocol_bytes().unwrap(),
+            vec![String::from("foo"), String::from("bar")].into_protocol_bytes().unwrap(),
+            Bytes(vec![42]).into_protocol_bytes().unwrap(),
+            vec![0, 0, 0, 1],
+            String::from("foo").into_protocol_bytes().unwrap(),
+            vec![I32(3)].into_protocol_bytes().unwrap(),
+            I32(7).into_protocol_bytes().unwrap(), // v2 generation_id, ignored
+        ]
+        .concat();
+        let subscription: ConsumerSubscription = bytes.into_protocol_type().unwrap();
+        assert_eq!(
+            ConsumerSubscription {
+                version: 1,
+                topics: vec![String::from("foo"), String::from("bar")],
+                user_data: Some(vec![42]),
+                owned_partitions: vec![TopicPartitions { topic: String::from("foo"), partitions: vec![3] }]
+            },
+            subscription
+        );
+    }
+
+    #[test]
+    fn verify_empty_consumer_subscription() {
+        let subscription: ConsumerSubscription = vec![].into_protocol_type().unwrap();
+        assert!(subscription.topics.is_empty());
+    }
+
+    #[test]
+    fn verify_empty_consumer_assignment() {
+        let assignment: ConsumerAssignment = vec![].into_protocol_type().unwrap();
+        assert!(assignment.assigned_partitions.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod protocol_primitives;
+pub mod protocol_records;
 pub mod protocol_serializable;
 
 pub mod protocol_request;
@@ -7,4 +8,6 @@ pub mod protocol_requests;
 pub mod protocol_response;
 pub mod protocol_responses;
 
+pub mod acl;
 pub mod api_verification;
+pub mod consumer_protocol;
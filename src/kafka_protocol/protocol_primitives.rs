@@ -17,6 +17,10 @@ pub enum ProtocolPrimitives {
     I32(i32),
     I64(i64),
     Boolean(bool),
+    Bytes(Vec<u8>),
+    VarInt(i32),
+    VarLong(i64),
+    UnsignedVarInt(u32),
 }
 
 impl ProtocolPrimitives {
@@ -27,8 +31,26 @@ impl ProtocolPrimitives {
     pub fn null_string() -> ProtocolPrimitives {
         I16(-1)
     }
+    // no tagged fields are ever sent
+    pub fn empty_tagged_fields() -> ProtocolPrimitives {
+        UnsignedVarInt(0)
+    }
 }
 
+/// Types used by flexible API versions, whose lengths are written
+/// as unsigned varints of length + 1 so that 0 can indicate null.
+#[derive(Clone, Debug)]
+pub struct CompactString(pub String);
+
+#[derive(Clone, Debug)]
+pub struct CompactNullableString(pub Option<String>);
+
+#[derive(Clone, Debug)]
+pub struct CompactArray<T>(pub Vec<T>);
+
+#[derive(Clone, Debug)]
+pub struct CompactNullableArray<T>(pub Option<Vec<T>>);
+
 impl ProtocolSerializable for String {
     fn into_protocol_bytes(self) -> ProtocolSerializeResult {
         I16(self.len() as i16).into_protocol_bytes().map(|string_size| [string_size.as_slice(), self.as_bytes()].concat())
@@ -53,11 +75,69 @@ impl ProtocolSerializable for ProtocolPrimitives {
             I32(i) => payload.write_i32::<BigEndian>(i),
             I64(i) => payload.write_i64::<BigEndian>(i),
             Boolean(b) => payload.write_i8(if b { 1 } else { 0 }),
+            Bytes(b) => payload.write_i32::<BigEndian>(b.len() as i32).and_then(|_| payload.write_all(b.as_slice())),
+            VarInt(i) => payload.write_all(zigzag_bytes(((i << 1) ^ (i >> 31)) as u32 as u64).as_slice()),
+            VarLong(i) => payload.write_all(zigzag_bytes(((i << 1) ^ (i >> 63)) as u64).as_slice()),
+            UnsignedVarInt(i) => payload.write_all(zigzag_bytes(u64::from(i)).as_slice()),
         };
         serialized.map(|_| payload)
     }
 }
 
+/// Unsigned LEB128 encoding of an already zigzag-encoded number,
+/// as used by the VARINT and VARLONG types.
+fn zigzag_bytes(mut zigzag: u64) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![];
+    while zigzag >= 0x80 {
+        bytes.push((zigzag as u8 & 0x7F) | 0x80);
+        zigzag >>= 7;
+    }
+    bytes.push(zigzag as u8);
+    bytes
+}
+
+impl ProtocolSerializable for CompactString {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        CompactNullableString(Some(self.0)).into_protocol_bytes()
+    }
+}
+
+impl ProtocolSerializable for CompactNullableString {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        match self.0 {
+            Some(string) => {
+                UnsignedVarInt(string.len() as u32 + 1).into_protocol_bytes().map(|string_size| [string_size.as_slice(), string.as_bytes()].concat())
+            }
+            None => UnsignedVarInt(0).into_protocol_bytes(),
+        }
+    }
+}
+
+impl<T> ProtocolSerializable for CompactArray<T>
+where
+    T: ProtocolSerializable,
+{
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        CompactNullableArray(Some(self.0)).into_protocol_bytes()
+    }
+}
+
+impl<T> ProtocolSerializable for CompactNullableArray<T>
+where
+    T: ProtocolSerializable,
+{
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        match self.0 {
+            Some(array) => {
+                let array_length = UnsignedVarInt(array.len() as u32 + 1).into_protocol_bytes();
+                let array_in_bytes = array.into_iter().map(|t| t.into_protocol_bytes()).collect::<Result<Vec<Vec<u8>>>>().map(|s| s.concat());
+                array_length.and_then(|array_length| array_in_bytes.map(|aib| [array_length, aib].concat()))
+            }
+            None => UnsignedVarInt(0).into_protocol_bytes(),
+        }
+    }
+}
+
 impl<T> ProtocolSerializable for Vec<T>
 where
     T: ProtocolSerializable,
@@ -99,6 +179,23 @@ mod tests {
         assert_eq!(vec![0, 0, 0, 1, 1], vec![Boolean(true)].into_protocol_bytes().unwrap());
         assert_eq!(vec![0, 0, 0, 2, 0, 1], vec![Boolean(false), Boolean(true)].into_protocol_bytes().unwrap());
 
+        assert_eq!(vec![0, 0, 0, 3, 1, 2, 3], Bytes(vec![1, 2, 3]).into_protocol_bytes().unwrap());
+
+        assert_eq!(vec![0], VarInt(0).into_protocol_bytes().unwrap());
+        assert_eq!(vec![1], VarInt(-1).into_protocol_bytes().unwrap());
+        assert_eq!(vec![2], VarInt(1).into_protocol_bytes().unwrap());
+        assert_eq!(vec![0x80, 0x01], VarInt(64).into_protocol_bytes().unwrap());
+        assert_eq!(vec![0xFF, 0xFF, 0xFF, 0xFF, 0x0F], VarInt(i32::MIN).into_protocol_bytes().unwrap());
+        assert_eq!(vec![0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01], VarLong(i64::MAX).into_protocol_bytes().unwrap());
+        assert_eq!(vec![0x80, 0x01], UnsignedVarInt(128).into_protocol_bytes().unwrap());
+
+        // compact array of 2, compact string of 3, 3 characters, null compact string
+        assert_eq!(
+            vec![3, 4, 102, 111, 111, 0],
+            CompactArray(vec![CompactNullableString(Some(String::from("foo"))), CompactNullableString(None)]).into_protocol_bytes().unwrap()
+        );
+        assert_eq!(vec![0], CompactNullableArray::<CompactString>(None).into_protocol_bytes().unwrap());
+
         // array of 2, string of 3, 3 characters, string of 3, 3 characters
         assert_eq!(
             vec![0, 0, 0, 2, 0, 3, 102, 111, 111, 0, 3, 98, 97, 114],
@@ -0,0 +1,321 @@
+use std::io::{Error, ErrorKind};
+
+#[cfg(feature = "lz4")]
+use crate::kafka_protocol::protocol_records::message_set::MAGIC_V0;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Compression codec, as selected by the lowest three bits of
+/// a record batch's (or legacy message's) attributes. Each codec
+/// other than `None` is only available with its cargo feature.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Compression {
+    None = 0,
+    Gzip = 1,
+    Snappy = 2,
+    Lz4 = 3,
+    Zstd = 4,
+}
+
+impl Compression {
+    pub fn from_attributes(attributes: i16) -> ProtocolDeserializeResult<Compression> {
+        match attributes & 0x07 {
+            0 => Ok(Compression::None),
+            1 => Ok(Compression::Gzip),
+            2 => Ok(Compression::Snappy),
+            3 => Ok(Compression::Lz4),
+            4 => Ok(Compression::Zstd),
+            codec => Err(DeserializeError::of(&format!("Unknown compression codec {}", codec))),
+        }
+    }
+
+    /// The cargo feature which enables this codec
+    pub fn feature(self) -> &'static str {
+        match self {
+            Compression::None => "",
+            Compression::Gzip => "gzip",
+            Compression::Snappy => "snappy",
+            Compression::Lz4 => "lz4",
+            Compression::Zstd => "zstd",
+        }
+    }
+}
+
+#[allow(unreachable_patterns)]
+pub fn compress(compression: Compression, bytes: Vec<u8>) -> ProtocolSerializeResult {
+    match compression {
+        Compression::None => Ok(bytes),
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => gzip::compress(&bytes),
+        #[cfg(feature = "snappy")]
+        Compression::Snappy => snappy::compress(&bytes),
+        #[cfg(feature = "lz4")]
+        Compression::Lz4 => lz4::compress(&bytes),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => zstd::compress(&bytes),
+        disabled => Err(Error::new(ErrorKind::InvalidInput, feature_disabled(disabled))),
+    }
+}
+
+#[allow(unreachable_patterns)]
+pub fn decompress(compression: Compression, bytes: Vec<u8>) -> ProtocolDeserializeResult<Vec<u8>> {
+    let decompressed: std::io::Result<Vec<u8>> = match compression {
+        Compression::None => Ok(bytes),
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => gzip::decompress(&bytes),
+        #[cfg(feature = "snappy")]
+        Compression::Snappy => snappy::decompress(&bytes),
+        #[cfg(feature = "lz4")]
+        Compression::Lz4 => lz4::decompress(&bytes),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => zstd::decompress(&bytes),
+        disabled => return Err(DeserializeError::of(&feature_disabled(disabled))),
+    };
+    decompressed.map_err(|e| DeserializeError::of(&format!("Failed to decompress {:?} records. {}", compression, e)))
+}
+
+/// Decompresses the value of a legacy wrapper message. Before 0.10, Kafka
+/// computed the LZ4 frame header checksum over the frame's magic number as
+/// well (KAFKA-3160), so the header of a magic v0 wrapper is corrected
+/// before decoding, as the Java client does.
+///
+#[cfg_attr(not(feature = "lz4"), allow(unused_variables))]
+pub fn decompress_message(compression: Compression, magic: i8, bytes: Vec<u8>) -> ProtocolDeserializeResult<Vec<u8>> {
+    match compression {
+        #[cfg(feature = "lz4")]
+        Compression::Lz4 if magic == MAGIC_V0 => decompress(compression, lz4::fix_header_checksum(bytes)),
+        _ => decompress(compression, bytes),
+    }
+}
+
+fn feature_disabled(compression: Compression) -> String {
+    format!("{:?} compression requires the '{}' feature of topiks-kafka-client, which is not enabled", compression, compression.feature())
+}
+
+#[cfg(feature = "gzip")]
+mod gzip {
+    use std::io::{Read, Result, Write};
+
+    use flate2::read::GzDecoder;
+    use flate2::write::GzEncoder;
+
+    pub fn compress(bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(bytes).and_then(|_| encoder.finish())
+    }
+
+    pub fn decompress(bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut decompressed: Vec<u8> = vec![];
+        GzDecoder::new(bytes).read_to_end(&mut decompressed).map(|_| decompressed)
+    }
+}
+
+/// Kafka's Java client writes snappy using the xerial framing, so that is what is
+/// written here. Both xerial framed and raw snappy blocks can be read.
+///
+#[cfg(feature = "snappy")]
+mod snappy {
+    use std::io::{Error, ErrorKind, Result};
+
+    use snap::raw::{Decoder, Encoder};
+
+    const XERIAL_HEADER: [u8; 16] = [0x82, b'S', b'N', b'A', b'P', b'P', b'Y', 0, 0, 0, 0, 1, 0, 0, 0, 1];
+    const XERIAL_MAGIC_LENGTH: usize = 8;
+    const XERIAL_BLOCK_SIZE: usize = 32 * 1024;
+
+    pub fn compress(bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut encoder = Encoder::new();
+        bytes
+            .chunks(XERIAL_BLOCK_SIZE)
+            .map(|block| encoder.compress_vec(block).map(|block| [(block.len() as i32).to_be_bytes().to_vec(), block].concat()))
+            .collect::<std::result::Result<Vec<Vec<u8>>, snap::Error>>()
+            .map(|blocks| [XERIAL_HEADER.to_vec(), blocks.concat()].concat())
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e))
+    }
+
+    pub fn decompress(bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut decoder = Decoder::new();
+        if bytes.len() >= XERIAL_HEADER.len() && bytes[0..XERIAL_MAGIC_LENGTH] == XERIAL_HEADER[0..XERIAL_MAGIC_LENGTH] {
+            let mut decompressed: Vec<u8> = vec![];
+            let mut blocks = &bytes[XERIAL_HEADER.len()..];
+            while !blocks.is_empty() {
+                if blocks.len() < 4 {
+                    return Err(Error::new(ErrorKind::UnexpectedEof, "Truncated xerial snappy block length"));
+                }
+                let block_length = i32::from_be_bytes([blocks[0], blocks[1], blocks[2], blocks[3]]);
+                if block_length < 0 || block_length as usize > blocks.len() - 4 {
+                    return Err(Error::new(ErrorKind::InvalidData, format!("Invalid xerial snappy block length {}", block_length)));
+                }
+                let (block, remaining_blocks) = blocks[4..].split_at(block_length as usize);
+                decompressed.append(&mut decoder.decompress_vec(block).map_err(|e| Error::new(ErrorKind::InvalidData, e))?);
+                blocks = remaining_blocks;
+            }
+            Ok(decompressed)
+        } else {
+            decoder.decompress_vec(bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+        }
+    }
+}
+
+#[cfg(feature = "lz4")]
+mod lz4 {
+    use std::io::{Read, Result, Write};
+
+    use lz4_flex::frame::{FrameDecoder, FrameEncoder};
+    use twox_hash::XxHash32;
+
+    pub fn compress(bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut encoder = FrameEncoder::new(vec![]);
+        encoder.write_all(bytes).and_then(|_| encoder.finish().map_err(|e| e.into()))
+    }
+
+    pub fn decompress(bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut decompressed: Vec<u8> = vec![];
+        FrameDecoder::new(bytes).read_to_end(&mut decompressed).map(|_| decompressed)
+    }
+
+    const FLG_CONTENT_SIZE: u8 = 0x08;
+    const FLG_DICTIONARY_ID: u8 = 0x01;
+
+    // The header checksum is the second byte of the xxhash32 of the frame
+    // descriptor, which starts after the 4 byte magic number and ends before
+    // the checksum. Frames too short to hold a header are left to the decoder.
+    pub fn fix_header_checksum(mut bytes: Vec<u8>) -> Vec<u8> {
+        if let Some(flg) = bytes.get(4).cloned() {
+            let checksum_offset = 6 + if flg & FLG_CONTENT_SIZE != 0 { 8 } else { 0 } + if flg & FLG_DICTIONARY_ID != 0 { 4 } else { 0 };
+            if checksum_offset < bytes.len() {
+                bytes[checksum_offset] = (XxHash32::oneshot(0, &bytes[4..checksum_offset]) >> 8) as u8;
+            }
+        }
+        bytes
+    }
+}
+
+#[cfg(feature = "zstd")]
+mod zstd {
+    use std::io::Result;
+
+    pub fn compress(bytes: &[u8]) -> Result<Vec<u8>> {
+        ::zstd::stream::encode_all(bytes, 0)
+    }
+
+    pub fn decompress(bytes: &[u8]) -> Result<Vec<u8>> {
+        ::zstd::stream::decode_all(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_records::message_set::tests::message;
+    use crate::kafka_protocol::protocol_records::message_set::*;
+    use crate::kafka_protocol::protocol_records::record_batch::*;
+    use crate::kafka_protocol::protocol_records::Record;
+
+    use super::*;
+
+    const CODECS: [Compression; 4] = [Compression::Gzip, Compression::Snappy, Compression::Lz4, Compression::Zstd];
+
+    fn enabled(compression: Compression) -> bool {
+        match compression {
+            Compression::None => true,
+            Compression::Gzip => cfg!(feature = "gzip"),
+            Compression::Snappy => cfg!(feature = "snappy"),
+            Compression::Lz4 => cfg!(feature = "lz4"),
+            Compression::Zstd => cfg!(feature = "zstd"),
+        }
+    }
+
+    fn record_batch(compression: Compression) -> RecordBatch {
+        RecordBatch {
+            base_offset: 0,
+            partition_leader_epoch: 0,
+            attributes: compression as i16,
+            first_timestamp: 1_500_000_000_000,
+            max_timestamp: 1_500_000_000_099,
+            producer_id: -1,
+            producer_epoch: -1,
+            base_sequence: -1,
+            records: (0..100)
+                .map(|i| Record { offset: i, timestamp: 1_500_000_000_000 + i, key: None, value: Some(vec![i as u8; 1024]), headers: vec![] })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn verify_compressed_record_batch() {
+        for compression in CODECS.iter().cloned() {
+            let batch = record_batch(compression);
+            match (enabled(compression), batch.clone().into_protocol_bytes()) {
+                (true, Ok(bytes)) => {
+                    assert!(bytes.len() < 100 * 1024, "{:?} did not compress", compression);
+                    assert_eq!(batch, de_record_batch(&bytes).unwrap().0);
+                }
+                (false, Err(e)) => assert!(e.to_string().contains(compression.feature())),
+                (enabled, result) => panic!("{:?} enabled: {}, but serialized to {:?}", compression, enabled, result.map(|bytes| bytes.len())),
+            }
+        }
+    }
+
+    #[test]
+    fn verify_disabled_codec_error() {
+        let uncompressed = record_batch(Compression::None).into_protocol_bytes().unwrap();
+        let records = uncompressed[61..].to_vec();
+        for compression in CODECS.iter().cloned().filter(|compression| !enabled(*compression)) {
+            match decompress(compression, records.clone()) {
+                Err(e) => assert!(e.error.contains(&format!("'{}' feature", compression.feature()))),
+                Ok(_) => panic!("{:?} should be disabled", compression),
+            }
+        }
+    }
+
+    #[test]
+    fn verify_compressed_wrapper_message() {
+        for compression in CODECS.iter().cloned().filter(|compression| enabled(*compression)) {
+            let inner_messages =
+                [message(0, MAGIC_V1, 0, 1_500_000_000_000, None, Some(vec![1])), message(1, MAGIC_V1, 0, 1_500_000_000_001, None, Some(vec![2]))]
+                    .concat();
+            let wrapper = message(41, MAGIC_V1, compression as i8, 1_500_000_000_001, None, Some(compress(compression, inner_messages).unwrap()));
+            let records = de_message_set(&wrapper).unwrap();
+            assert_eq!(vec![(40, 1_500_000_000_000), (41, 1_500_000_000_001)], records.iter().map(|r| (r.offset, r.timestamp)).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn verify_nested_compressed_wrapper_message() {
+        for compression in CODECS.iter().cloned().filter(|compression| enabled(*compression)) {
+            let inner_wrapper = message(
+                0,
+                MAGIC_V1,
+                compression as i8,
+                1_500_000_000_000,
+                None,
+                Some(compress(compression, message(0, MAGIC_V1, 0, 1_500_000_000_000, None, Some(vec![1]))).unwrap()),
+            );
+            let wrapper = message(0, MAGIC_V1, compression as i8, 1_500_000_000_000, None, Some(compress(compression, inner_wrapper).unwrap()));
+            match de_message_set(&wrapper) {
+                Err(e) => assert!(e.error.contains("nested within a compressed wrapper")),
+                Ok(records) => panic!("{:?} nested wrapper deserialized to {:?}", compression, records),
+            }
+        }
+    }
+
+    // LZ4 frame header as written by Kafka 0.9 (FLG 0x60, BD 0x40), with the
+    // header checksum computed over the magic number as well: 0x1A, not 0x82
+    #[cfg(feature = "lz4")]
+    const KAFKA_0_9_LZ4_FRAME_HEADER: [u8; 7] = [0x04, 0x22, 0x4D, 0x18, 0x60, 0x40, 0x1A];
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn verify_v0_lz4_wrapper_message_with_broken_header_checksum() {
+        let inner_message = message(0, MAGIC_V0, 0, 0, None, Some(b"kafka".to_vec()));
+        // a single uncompressed block followed by the end mark
+        let frame =
+            [KAFKA_0_9_LZ4_FRAME_HEADER.to_vec(), (inner_message.len() as u32 | 0x8000_0000).to_le_bytes().to_vec(), inner_message, vec![0, 0, 0, 0]]
+                .concat();
+        assert!(decompress(Compression::Lz4, frame.clone()).is_err());
+
+        let wrapper = message(0, MAGIC_V0, Compression::Lz4 as i8, 0, None, Some(frame));
+        let records = de_message_set(&wrapper).unwrap();
+        assert_eq!(vec![Record { offset: 0, timestamp: NO_TIMESTAMP, key: None, value: Some(b"kafka".to_vec()), headers: vec![] }], records);
+    }
+}
@@ -0,0 +1,51 @@
+/// CRC-32C (Castagnoli), as used by magic v2 record batches.
+///
+pub fn crc32c(bytes: &[u8]) -> u32 {
+    checksum(&CRC32C_TABLE, bytes)
+}
+
+/// CRC-32 (IEEE), as used by legacy v0 and v1 messages.
+///
+pub fn crc32(bytes: &[u8]) -> u32 {
+    checksum(&CRC32_TABLE, bytes)
+}
+
+const CRC32C_TABLE: [u32; 256] = table(0x82F6_3B78);
+const CRC32_TABLE: [u32; 256] = table(0xEDB8_8320);
+
+fn checksum(table: &[u32; 256], bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, byte| table[((crc ^ u32::from(*byte)) & 0xFF) as usize] ^ (crc >> 8))
+}
+
+const fn table(polynomial: u32) -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut crc = n as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ polynomial } else { crc >> 1 };
+            bit += 1;
+        }
+        table[n] = crc;
+        n += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_crc32c() {
+        assert_eq!(0, crc32c(&[]));
+        assert_eq!(0xE306_9283, crc32c(b"123456789"));
+    }
+
+    #[test]
+    fn verify_crc32() {
+        assert_eq!(0, crc32(&[]));
+        assert_eq!(0xCBF4_3926, crc32(b"123456789"));
+    }
+}
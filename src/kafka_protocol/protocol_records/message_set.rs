@@ -0,0 +1,190 @@
+use crate::kafka_protocol::protocol_records::compression::*;
+use crate::kafka_protocol::protocol_records::crc::crc32;
+use crate::kafka_protocol::protocol_records::is_complete_entry;
+use crate::kafka_protocol::protocol_records::Record;
+use crate::kafka_protocol::protocol_records::LOG_OVERHEAD;
+use crate::kafka_protocol::protocol_serializable::*;
+
+pub const MAGIC_V0: i8 = 0;
+pub const MAGIC_V1: i8 = 1;
+
+// Kafka's timestamp for v0 messages, which have none
+pub const NO_TIMESTAMP: i64 = -1;
+
+const TIMESTAMP_TYPE_LOG_APPEND_TIME: i8 = 0x08;
+
+// crc, magic, attributes, key length and value length
+const MIN_MESSAGE_SIZE: usize = 14;
+
+/// Deserializes every complete message in a legacy (pre-0.11) message set into
+/// the same `Record` representation as magic v2 record batches. A partial
+/// message at the end of the message set is ignored.
+///
+pub fn de_message_set(bytes: &[u8]) -> ProtocolDeserializeResult<Vec<Record>> {
+    de_messages(bytes, false)
+}
+
+/// Deserializes a single message, including its offset and size. A compressed
+/// wrapper message deserializes into all of the messages nested within it.
+///
+pub fn de_message(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Vec<Record>>> {
+    de_message_entry(bytes, false)
+}
+
+fn de_messages(bytes: &[u8], nested: bool) -> ProtocolDeserializeResult<Vec<Record>> {
+    let mut records: Vec<Record> = vec![];
+    let mut bytes = bytes;
+    while is_complete_entry(bytes) {
+        let (mut message_records, remaining_bytes) = de_message_entry(bytes, nested)?;
+        records.append(&mut message_records);
+        bytes = remaining_bytes;
+    }
+    Ok(records)
+}
+
+// Kafka allows a single level of compression, so a message nested in a
+// compressed wrapper must not be a compressed wrapper itself
+fn de_message_entry(bytes: &[u8], nested: bool) -> ProtocolDeserializeResult<DynamicSize<'_, Vec<Record>>> {
+    if bytes.len() < LOG_OVERHEAD {
+        return Err(DeserializeError::of(&format!("Message requires at least {} bytes but only {} remain", LOG_OVERHEAD, bytes.len())));
+    }
+
+    let offset = de_i64(&bytes[0..8])?;
+    let message_end = de_i32(&bytes[8..LOG_OVERHEAD]).and_then(|message_size| {
+        let message_end = LOG_OVERHEAD as i64 + i64::from(message_size);
+        if (message_size as i64) < MIN_MESSAGE_SIZE as i64 || message_end > bytes.len() as i64 {
+            Err(DeserializeError::of(&format!("Invalid message size {} with {} bytes remaining", message_size, bytes.len() - LOG_OVERHEAD)))
+        } else {
+            Ok(message_end as usize)
+        }
+    })?;
+    let message = &bytes[LOG_OVERHEAD..message_end];
+
+    let crc = de_i32(&message[0..4])? as u32;
+    let computed_crc = crc32(&message[4..]);
+    if crc != computed_crc {
+        return Err(DeserializeError::of(&format!("Message is corrupt. CRC {:#010X} does not match computed {:#010X}", crc, computed_crc)));
+    }
+
+    let magic = message[4] as i8;
+    let attributes = message[5] as i8;
+    let timestamp_and_key_value = match magic {
+        MAGIC_V0 => Ok((NO_TIMESTAMP, &message[6..])),
+        MAGIC_V1 if message.len() >= MIN_MESSAGE_SIZE + 8 => de_i64(&message[6..14]).map(|timestamp| (timestamp, &message[14..])),
+        MAGIC_V1 => Err(DeserializeError::of(&format!("Invalid v1 message size {}", message.len()))),
+        _ => Err(DeserializeError::of(&format!("Unexpected message magic {}", magic))),
+    };
+
+    let record = timestamp_and_key_value.and_then(|(timestamp, bytes)| {
+        de_bytes(bytes).and_then(|(key, bytes)| {
+            de_bytes(bytes).and_then(|(value, bytes)| {
+                if bytes.is_empty() {
+                    Ok(Record { offset, timestamp, key, value, headers: vec![] })
+                } else {
+                    Err(DeserializeError::of(&format!("Unexpected {} bytes at end of message", bytes.len())))
+                }
+            })
+        })
+    })?;
+
+    let records = match Compression::from_attributes(i16::from(attributes))? {
+        Compression::None => Ok(vec![record]),
+        _ if nested => Err(DeserializeError::of("Unexpected compressed message nested within a compressed wrapper message")),
+        compression => {
+            let Record { offset, timestamp, value, .. } = record;
+            value
+                .ok_or_else(|| DeserializeError::of("Unexpected null value for compressed wrapper message"))
+                .and_then(|value| decompress_message(compression, magic, value))
+                .and_then(|inner_bytes| de_messages(&inner_bytes, true))
+                .map(|inner_records| unwrap_inner_records(magic, attributes, offset, timestamp, inner_records))
+        }
+    }?;
+
+    Ok((records, &bytes[message_end..]))
+}
+
+/// Inner messages of a v1 wrapper carry offsets relative to the first inner
+/// message, with the wrapper holding the absolute offset of the last. When
+/// the wrapper uses log append time, its timestamp applies to every inner message.
+///
+fn unwrap_inner_records(magic: i8, attributes: i8, wrapper_offset: i64, wrapper_timestamp: i64, inner_records: Vec<Record>) -> Vec<Record> {
+    match magic {
+        MAGIC_V0 => inner_records,
+        _ => {
+            let last_relative_offset = inner_records.last().map(|record| record.offset).unwrap_or(0);
+            let log_append_time = attributes & TIMESTAMP_TYPE_LOG_APPEND_TIME != 0;
+            inner_records
+                .into_iter()
+                .map(|record| Record {
+                    offset: wrapper_offset - last_relative_offset + record.offset,
+                    timestamp: if log_append_time { wrapper_timestamp } else { record.timestamp },
+                    ..record
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+    use crate::kafka_protocol::protocol_records::record_batch::RecordBatch;
+
+    use super::*;
+
+    pub(crate) fn message(offset: i64, magic: i8, attributes: i8, timestamp: i64, key: Option<Vec<u8>>, value: Option<Vec<u8>>) -> Vec<u8> {
+        let timestamp = if magic == MAGIC_V1 { I64(timestamp).into_protocol_bytes().unwrap() } else { vec![] };
+        let key = key.map(|key| Bytes(key).into_protocol_bytes().unwrap()).unwrap_or_else(|| I32(-1).into_protocol_bytes().unwrap());
+        let value = value.map(|value| Bytes(value).into_protocol_bytes().unwrap()).unwrap_or_else(|| I32(-1).into_protocol_bytes().unwrap());
+        let crc_covered = [vec![magic as u8, attributes as u8], timestamp, key, value].concat();
+        let message = [(crc32(&crc_covered) as i32).to_be_bytes().to_vec(), crc_covered].concat();
+        [offset.to_be_bytes().to_vec(), (message.len() as i32).to_be_bytes().to_vec(), message].concat()
+    }
+
+    #[test]
+    fn verify_message_set() {
+        let message_set = [
+            message(5, MAGIC_V0, 0, 0, None, Some(vec![1, 2])),
+            message(6, MAGIC_V1, 0, 1_500_000_000_000, Some(vec![3]), None),
+            message(7, MAGIC_V1, 0, 1_500_000_000_001, None, Some(vec![4])),
+        ]
+        .concat();
+
+        // the last message is truncated, as a broker may do when limited by max_bytes
+        let records = de_message_set(&message_set[0..message_set.len() - 1]).unwrap();
+        assert_eq!(
+            vec![
+                Record { offset: 5, timestamp: NO_TIMESTAMP, key: None, value: Some(vec![1, 2]), headers: vec![] },
+                Record { offset: 6, timestamp: 1_500_000_000_000, key: Some(vec![3]), value: None, headers: vec![] },
+            ],
+            records
+        );
+        assert_eq!(3, de_message_set(&message_set).unwrap().len());
+    }
+
+    #[test]
+    fn verify_corrupt_message() {
+        let mut bytes = message(5, MAGIC_V1, 0, 1_500_000_000_000, Some(vec![3]), Some(vec![4]));
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        assert!(de_message_set(&bytes).is_err());
+    }
+
+    #[test]
+    fn verify_mixed_record_set() {
+        let batch = RecordBatch {
+            base_offset: 8,
+            partition_leader_epoch: 0,
+            attributes: 0,
+            first_timestamp: 1_500_000_000_002,
+            max_timestamp: 1_500_000_000_002,
+            producer_id: -1,
+            producer_epoch: -1,
+            base_sequence: -1,
+            records: vec![Record { offset: 8, timestamp: 1_500_000_000_002, key: None, value: Some(vec![5]), headers: vec![] }],
+        };
+        let record_set = [message(7, MAGIC_V1, 0, 1_500_000_000_001, None, Some(vec![4])), batch.into_protocol_bytes().unwrap()].concat();
+        let records: Vec<Record> = record_set.into_protocol_type().unwrap();
+        assert_eq!(vec![7, 8], records.iter().map(|record| record.offset).collect::<Vec<i64>>());
+    }
+}
@@ -0,0 +1,67 @@
+use crate::kafka_protocol::protocol_records::message_set::de_message;
+use crate::kafka_protocol::protocol_records::record_batch::de_record_batch;
+use crate::kafka_protocol::protocol_serializable::*;
+
+pub mod compression;
+pub mod crc;
+pub mod message_set;
+pub mod record_batch;
+
+/// A single record, independent of the on-disk format it was read from.
+/// Offsets and timestamps are absolute rather than deltas.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub offset: i64,
+    pub timestamp: i64,
+    pub key: Option<Vec<u8>>,
+    pub value: Option<Vec<u8>>,
+    pub headers: Vec<Header>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Header {
+    pub key: String,
+    pub value: Option<Vec<u8>>,
+}
+
+// every record batch or legacy message starts with an offset and a size
+// which does not include these 12 bytes
+pub(crate) const LOG_OVERHEAD: usize = 12;
+// the magic byte follows the offset, size and a 4 byte crc or partition leader epoch
+pub(crate) const MAGIC_OFFSET: usize = 16;
+
+/// Deserializes a record set (e.g. from a FetchResponse) holding magic v2
+/// record batches and/or legacy v0/v1 messages into records. Control
+/// batches are skipped, and a partial batch or message at the end of the
+/// record set, which brokers may return when a fetch is limited by size,
+/// is ignored.
+///
+impl ProtocolDeserializable<Vec<Record>> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<Vec<Record>> {
+        let mut records: Vec<Record> = vec![];
+        let mut bytes = self.as_slice();
+        while is_complete_entry(bytes) {
+            let remaining_bytes = match bytes.get(MAGIC_OFFSET).map(|magic| *magic as i8) {
+                Some(record_batch::MAGIC) => de_record_batch(bytes).map(|(mut batch, remaining_bytes)| {
+                    if !batch.is_control() {
+                        records.append(&mut batch.records);
+                    }
+                    remaining_bytes
+                }),
+                Some(message_set::MAGIC_V0) | Some(message_set::MAGIC_V1) => de_message(bytes).map(|(mut messages, remaining_bytes)| {
+                    records.append(&mut messages);
+                    remaining_bytes
+                }),
+                Some(magic) => Err(DeserializeError::of(&format!("Unsupported record magic {}", magic))),
+                None => Err(DeserializeError::of("Record set entry too small to hold a magic byte")),
+            }?;
+            bytes = remaining_bytes;
+        }
+        Ok(records)
+    }
+}
+
+pub(crate) fn is_complete_entry(bytes: &[u8]) -> bool {
+    bytes.len() >= LOG_OVERHEAD && de_i32(&bytes[8..LOG_OVERHEAD]).map(|size| bytes.len() - LOG_OVERHEAD >= size.max(0) as usize).unwrap_or(false)
+}
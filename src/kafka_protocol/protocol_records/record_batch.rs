@@ -0,0 +1,412 @@
+use std::convert::TryFrom;
+use std::io::Result as IOResult;
+use std::io::{Error, ErrorKind};
+
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+use crate::kafka_protocol::protocol_records::compression::*;
+use crate::kafka_protocol::protocol_records::crc::crc32c;
+use crate::kafka_protocol::protocol_records::is_complete_entry;
+use crate::kafka_protocol::protocol_records::Header;
+use crate::kafka_protocol::protocol_records::Record;
+use crate::kafka_protocol::protocol_records::LOG_OVERHEAD;
+use crate::kafka_protocol::protocol_records::MAGIC_OFFSET;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Magic v2 record batch
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordBatch {
+    pub base_offset: i64,
+    pub partition_leader_epoch: i32,
+    pub attributes: i16,
+    pub first_timestamp: i64,
+    pub max_timestamp: i64,
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    pub base_sequence: i32,
+    pub records: Vec<Record>,
+}
+
+pub const MAGIC: i8 = 2;
+
+const CRC_OFFSET: usize = 17;
+const ATTRIBUTES_OFFSET: usize = 21;
+const RECORDS_COUNT_OFFSET: usize = 57;
+const RECORDS_OFFSET: usize = 61;
+
+impl RecordBatch {
+    pub fn compression(&self) -> ProtocolDeserializeResult<Compression> {
+        Compression::from_attributes(self.attributes)
+    }
+    pub fn is_transactional(&self) -> bool {
+        self.attributes & 0x10 != 0
+    }
+    pub fn is_control(&self) -> bool {
+        self.attributes & 0x20 != 0
+    }
+}
+
+impl ProtocolSerializable for RecordBatch {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let base_offset = self.base_offset;
+        let partition_leader_epoch = self.partition_leader_epoch;
+        let attributes = self.attributes;
+        let first_timestamp = self.first_timestamp;
+        let max_timestamp = self.max_timestamp;
+        let producer_id = self.producer_id;
+        let producer_epoch = self.producer_epoch;
+        let base_sequence = self.base_sequence;
+        let last_offset_delta = self
+            .records
+            .iter()
+            .map(|record| offset_delta(record.offset, base_offset))
+            .collect::<IOResult<Vec<i32>>>()?
+            .into_iter()
+            .max()
+            .unwrap_or(0);
+        let records_count = self.records.len() as i32;
+
+        let compression = Compression::from_attributes(attributes).map_err(|e| Error::new(ErrorKind::InvalidInput, e.error));
+        let records = self
+            .records
+            .into_iter()
+            .map(|record| serialize_record(record, base_offset, first_timestamp))
+            .collect::<IOResult<Vec<Vec<u8>>>>()
+            .map(|records| records.concat());
+        let records = compression.and_then(|compression| records.and_then(|records| compress(compression, records)));
+
+        let crc_covered = records.and_then(|records| {
+            vec![
+                I16(attributes),
+                I32(last_offset_delta),
+                I64(first_timestamp),
+                I64(max_timestamp),
+                I64(producer_id),
+                I16(producer_epoch),
+                I32(base_sequence),
+                I32(records_count),
+            ]
+            .into_iter()
+            .map(|primitive| primitive.into_protocol_bytes())
+            .collect::<IOResult<Vec<Vec<u8>>>>()
+            .map(|header| [header.concat(), records].concat())
+        });
+
+        crc_covered.and_then(|crc_covered| {
+            let crc = crc32c(&crc_covered) as i32;
+            let batch_length = (ATTRIBUTES_OFFSET - LOG_OVERHEAD + crc_covered.len()) as i32;
+            vec![I64(base_offset), I32(batch_length), I32(partition_leader_epoch), I8(MAGIC), I32(crc)]
+                .into_iter()
+                .map(|primitive| primitive.into_protocol_bytes())
+                .collect::<IOResult<Vec<Vec<u8>>>>()
+                .map(|header| [header.concat(), crc_covered].concat())
+        })
+    }
+}
+
+fn serialize_record(record: Record, base_offset: i64, first_timestamp: i64) -> ProtocolSerializeResult {
+    let Record { offset, timestamp, key, value, headers } = record;
+    let headers_count = headers.len() as i32;
+    let headers = headers.into_iter().map(serialize_header).collect::<IOResult<Vec<Vec<u8>>>>().map(|headers| headers.concat());
+
+    let timestamp_delta = timestamp.checked_sub(first_timestamp).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("Record timestamp {} is out of range of the batch's first timestamp {}", timestamp, first_timestamp),
+        )
+    })?;
+    let offset_delta = offset_delta(offset, base_offset)?;
+
+    let body = headers.and_then(|headers| {
+        vec![
+            I8(0).into_protocol_bytes(),
+            VarLong(timestamp_delta).into_protocol_bytes(),
+            VarInt(offset_delta).into_protocol_bytes(),
+            serialize_varint_bytes(key),
+            serialize_varint_bytes(value),
+            VarInt(headers_count).into_protocol_bytes(),
+        ]
+        .into_iter()
+        .collect::<IOResult<Vec<Vec<u8>>>>()
+        .map(|body| [body.concat(), headers].concat())
+    });
+
+    body.and_then(|body| VarInt(body.len() as i32).into_protocol_bytes().map(|length| [length, body].concat()))
+}
+
+// offsets are sent as i32 deltas from the batch's base offset
+fn offset_delta(offset: i64, base_offset: i64) -> IOResult<i32> {
+    offset.checked_sub(base_offset).and_then(|delta| i32::try_from(delta).ok()).ok_or_else(|| {
+        Error::new(ErrorKind::InvalidInput, format!("Record offset {} is out of range of the batch's base offset {}", offset, base_offset))
+    })
+}
+
+fn serialize_header(header: Header) -> ProtocolSerializeResult {
+    let Header { key, value } = header;
+    serialize_varint_bytes(Some(key.into_bytes())).and_then(|key| serialize_varint_bytes(value).map(|value| [key, value].concat()))
+}
+
+fn serialize_varint_bytes(bytes: Option<Vec<u8>>) -> ProtocolSerializeResult {
+    match bytes {
+        Some(bytes) => VarInt(bytes.len() as i32).into_protocol_bytes().map(|length| [length, bytes].concat()),
+        None => VarInt(-1).into_protocol_bytes(),
+    }
+}
+
+/// Deserializes the record batches of a record set. A partial batch
+/// at the end of the record set is ignored.
+///
+impl ProtocolDeserializable<Vec<RecordBatch>> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<Vec<RecordBatch>> {
+        let mut batches: Vec<RecordBatch> = vec![];
+        let mut bytes = self.as_slice();
+        while is_complete_entry(bytes) {
+            let (batch, remaining_bytes) = de_record_batch(bytes)?;
+            batches.push(batch);
+            bytes = remaining_bytes;
+        }
+        Ok(batches)
+    }
+}
+
+pub fn de_record_batch(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, RecordBatch>> {
+    if bytes.len() < RECORDS_OFFSET {
+        return Err(DeserializeError::of(&format!("Record batch requires at least {} bytes but only {} remain", RECORDS_OFFSET, bytes.len())));
+    }
+
+    let batch_end = de_i32(&bytes[8..LOG_OVERHEAD]).and_then(|batch_length| {
+        let batch_end = LOG_OVERHEAD as i64 + i64::from(batch_length);
+        if batch_end < RECORDS_OFFSET as i64 || batch_end > bytes.len() as i64 {
+            Err(DeserializeError::of(&format!("Invalid record batch length {} with {} bytes remaining", batch_length, bytes.len() - LOG_OVERHEAD)))
+        } else {
+            Ok(batch_end as usize)
+        }
+    });
+
+    let batch_end = batch_end.and_then(|batch_end| match bytes[MAGIC_OFFSET] as i8 {
+        MAGIC => de_i32(&bytes[CRC_OFFSET..ATTRIBUTES_OFFSET]).and_then(|crc| {
+            let computed_crc = crc32c(&bytes[ATTRIBUTES_OFFSET..batch_end]);
+            if crc as u32 == computed_crc {
+                Ok(batch_end)
+            } else {
+                Err(DeserializeError::of(&format!(
+                    "Record batch is corrupt. CRC {:#010X} does not match computed {:#010X}",
+                    crc as u32, computed_crc
+                )))
+            }
+        }),
+        magic => Err(DeserializeError::of(&format!("Unexpected record batch magic {}", magic))),
+    })?;
+
+    let base_offset = de_i64(&bytes[0..8])?;
+    let partition_leader_epoch = de_i32(&bytes[12..16])?;
+    let attributes = de_i16(&bytes[21..23])?;
+    let first_timestamp = de_i64(&bytes[27..35])?;
+    let max_timestamp = de_i64(&bytes[35..43])?;
+    let producer_id = de_i64(&bytes[43..51])?;
+    let producer_epoch = de_i16(&bytes[51..53])?;
+    let base_sequence = de_i32(&bytes[53..RECORDS_COUNT_OFFSET])?;
+    let records_count = de_i32(&bytes[RECORDS_COUNT_OFFSET..RECORDS_OFFSET])?;
+
+    let records = Compression::from_attributes(attributes)
+        .and_then(|compression| decompress(compression, bytes[RECORDS_OFFSET..batch_end].to_vec()))
+        .and_then(|records| de_records(&records, records_count, base_offset, first_timestamp))?;
+
+    let batch = RecordBatch {
+        base_offset,
+        partition_leader_epoch,
+        attributes,
+        first_timestamp,
+        max_timestamp,
+        producer_id,
+        producer_epoch,
+        base_sequence,
+        records,
+    };
+    Ok((batch, &bytes[batch_end..]))
+}
+
+fn de_records(bytes: &[u8], records_count: i32, base_offset: i64, first_timestamp: i64) -> ProtocolDeserializeResult<Vec<Record>> {
+    let mut records: Vec<Record> = Vec::with_capacity(records_count.max(0).min(bytes.len() as i32) as usize);
+    let mut bytes = bytes;
+    for _ in 0..records_count {
+        let (record, remaining_bytes) = de_record(bytes, base_offset, first_timestamp)?;
+        records.push(record);
+        bytes = remaining_bytes;
+    }
+    if bytes.is_empty() {
+        Ok(records)
+    } else {
+        Err(DeserializeError::of(&format!("Unexpected {} bytes after {} records", bytes.len(), records_count)))
+    }
+}
+
+fn de_record(bytes: &[u8], base_offset: i64, first_timestamp: i64) -> ProtocolDeserializeResult<DynamicSize<'_, Record>> {
+    de_varint(bytes).and_then(|(length, bytes)| {
+        if length < 1 || length as usize > bytes.len() {
+            return Err(DeserializeError::of(&format!("Invalid record length {} with {} bytes remaining", length, bytes.len())));
+        }
+        let (record_bytes, remaining_bytes) = bytes.split_at(length as usize);
+
+        let record = de_varlong(&record_bytes[1..]).and_then(|(timestamp_delta, bytes)| {
+            de_varint(bytes).and_then(|(offset_delta, bytes)| {
+                de_varint_bytes(bytes).and_then(|(key, bytes)| {
+                    de_varint_bytes(bytes).and_then(|(value, bytes)| {
+                        de_headers(bytes).and_then(|(headers, bytes)| {
+                            let offset = base_offset.checked_add(i64::from(offset_delta)).ok_or_else(|| {
+                                DeserializeError::of(&format!("Record offset delta {} overflows base offset {}", offset_delta, base_offset))
+                            })?;
+                            let timestamp = first_timestamp.checked_add(timestamp_delta).ok_or_else(|| {
+                                DeserializeError::of(&format!(
+                                    "Record timestamp delta {} overflows first timestamp {}",
+                                    timestamp_delta, first_timestamp
+                                ))
+                            })?;
+                            Ok((Record { offset, timestamp, key, value, headers }, bytes))
+                        })
+                    })
+                })
+            })
+        });
+
+        record.and_then(|(record, bytes)| {
+            if bytes.is_empty() {
+                Ok((record, remaining_bytes))
+            } else {
+                Err(DeserializeError::of(&format!("Unexpected {} bytes at end of record", bytes.len())))
+            }
+        })
+    })
+}
+
+fn de_headers(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Vec<Header>>> {
+    let (headers_count, mut bytes) = de_varint(bytes)?;
+    let mut headers: Vec<Header> = vec![];
+    for _ in 0..headers_count {
+        let (header, remaining_bytes) = de_varint_bytes(bytes).and_then(|(key, bytes)| {
+            let key = key
+                .ok_or_else(|| DeserializeError::of("Unexpected null header key"))
+                .and_then(|key| String::from_utf8(key).map_err(|e| DeserializeError::of(&format!("Failed to deserialize header key. {}", e))));
+            key.and_then(|key| de_varint_bytes(bytes).map(|(value, bytes)| (Header { key, value }, bytes)))
+        })?;
+        headers.push(header);
+        bytes = remaining_bytes;
+    }
+    Ok((headers, bytes))
+}
+
+fn de_varint_bytes(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Option<Vec<u8>>>> {
+    de_varint(bytes).and_then(|(length, bytes)| match length {
+        -1 => Ok((None, bytes)),
+        length if length >= 0 && length as usize <= bytes.len() => Ok((Some(bytes[..length as usize].to_vec()), &bytes[length as usize..])),
+        length => Err(DeserializeError::of(&format!("Invalid length {} with {} bytes remaining", length, bytes.len()))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::collection::vec;
+    use proptest::option;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    type KeyValueHeaders = (Option<Vec<u8>>, Option<Vec<u8>>, Vec<(String, Option<Vec<u8>>)>);
+
+    fn record_batch(records: Vec<KeyValueHeaders>) -> RecordBatch {
+        let records = records
+            .into_iter()
+            .enumerate()
+            .map(|(i, (key, value, headers))| Record {
+                offset: 100 + i as i64,
+                timestamp: 1_500_000_000_000 + i as i64 * 7,
+                key,
+                value,
+                headers: headers.into_iter().map(|(key, value)| Header { key, value }).collect(),
+            })
+            .collect();
+        RecordBatch {
+            base_offset: 100,
+            partition_leader_epoch: 3,
+            attributes: 0,
+            first_timestamp: 1_500_000_000_000,
+            max_timestamp: 1_500_000_000_100,
+            producer_id: -1,
+            producer_epoch: -1,
+            base_sequence: -1,
+            records,
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn verify_serde_for_record_batch(
+            ref records in vec((option::of(vec(any::<u8>(), 0..64)), option::of(vec(any::<u8>(), 0..256)), vec((".*", option::of(vec(any::<u8>(), 0..16))), 0..3)), 0..16)
+        ) {
+            let batch = record_batch(records.clone());
+            let bytes = batch.clone().into_protocol_bytes().unwrap();
+            match de_record_batch(&bytes) {
+                Ok((deserialized, remaining_bytes)) => {
+                    assert!(remaining_bytes.is_empty());
+                    assert_eq!(batch, deserialized);
+                },
+                Err(e) => panic!("{:?}", e)
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn verify_corrupt_record_batch(index in 17usize..61, flip in 1u8..) {
+            let batch = record_batch(vec![(Some(vec![1, 2, 3]), Some(vec![4, 5, 6]), vec![(String::from("foo"), None)])]);
+            let mut bytes = batch.into_protocol_bytes().unwrap();
+            bytes[index] ^= flip;
+            assert!(de_record_batch(&bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn verify_record_batch_with_overflowing_deltas() {
+        let batch = record_batch(vec![(None, Some(vec![1]), vec![]), (None, Some(vec![2]), vec![])]);
+        let bytes = batch.into_protocol_bytes().unwrap();
+
+        // the base offset is not covered by the CRC
+        let overflowing_offset = [i64::MAX.to_be_bytes().to_vec(), bytes[8..].to_vec()].concat();
+        assert!(de_record_batch(&overflowing_offset).unwrap_err().error.contains("overflows base offset"));
+
+        let mut overflowing_timestamp = bytes;
+        overflowing_timestamp[27..35].copy_from_slice(&i64::MAX.to_be_bytes());
+        let crc = crc32c(&overflowing_timestamp[ATTRIBUTES_OFFSET..]);
+        overflowing_timestamp[CRC_OFFSET..ATTRIBUTES_OFFSET].copy_from_slice(&crc.to_be_bytes());
+        assert!(de_record_batch(&overflowing_timestamp).unwrap_err().error.contains("overflows first timestamp"));
+    }
+
+    #[test]
+    fn verify_record_batch_with_out_of_range_records() {
+        let batch = record_batch(vec![(None, Some(vec![1]), vec![])]);
+        let distant_offset = RecordBatch { records: vec![Record { offset: i64::MAX, ..batch.records[0].clone() }], ..batch.clone() };
+        assert!(distant_offset.into_protocol_bytes().is_err());
+
+        let distant_timestamp =
+            RecordBatch { first_timestamp: i64::MAX, records: vec![Record { timestamp: i64::MIN, ..batch.records[0].clone() }], ..batch };
+        assert!(distant_timestamp.into_protocol_bytes().is_err());
+    }
+
+    #[test]
+    fn verify_record_set() {
+        let first = record_batch(vec![(None, Some(vec![1]), vec![])]);
+        let second = RecordBatch { base_offset: 101, ..record_batch(vec![(None, Some(vec![2]), vec![])]) };
+        let second = RecordBatch { records: vec![Record { offset: 101, ..second.records[0].clone() }], ..second };
+        let first_bytes = first.clone().into_protocol_bytes().unwrap();
+        let second_bytes = second.clone().into_protocol_bytes().unwrap();
+
+        // the second batch is truncated, as a broker may do when limited by max_bytes
+        let record_set = [first_bytes.clone(), second_bytes[0..second_bytes.len() - 1].to_vec()].concat();
+        let batches: Vec<RecordBatch> = record_set.into_protocol_type().unwrap();
+        assert_eq!(vec![first.clone()], batches);
+
+        let record_set = [first_bytes, second_bytes].concat();
+        let batches: Vec<RecordBatch> = record_set.into_protocol_type().unwrap();
+        assert_eq!(vec![first, second], batches);
+    }
+}
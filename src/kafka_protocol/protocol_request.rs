@@ -1,4 +1,5 @@
 use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives;
 use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
 use crate::kafka_protocol::protocol_serializable::ProtocolSerializeResult;
 use crate::kafka_protocol::protocol_serializable::*;
@@ -14,7 +15,13 @@ pub struct Request<T: ProtocolSerializable> {
 impl<A: ProtocolSerializable + KafkaApiVersioned> Request<A> {
     pub fn of(request_message: A) -> Request<A> {
         Request {
-            header: RequestHeader { api_key: A::api_key(), api_version: A::version(), correlation_id: 42, client_id: String::from("topiks") },
+            header: RequestHeader {
+                api_key: A::api_key(),
+                api_version: A::version(),
+                correlation_id: 0,
+                client_id: String::from("topiks"),
+                flexible: A::flexible(),
+            },
             request_message,
         }
     }
@@ -39,16 +46,23 @@ where
 pub struct RequestHeader {
     pub api_key: i16,
     pub api_version: i16,
+    /// assigned by the connection the request is sent on
     pub correlation_id: i32,
     pub client_id: String,
+    /// flexible API versions use a header (and response header) with tagged fields
+    pub flexible: bool,
 }
 
 impl ProtocolSerializable for RequestHeader {
     fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let flexible = self.flexible;
         I16(self.api_key).into_protocol_bytes().and_then(|api_key| {
             I16(self.api_version).into_protocol_bytes().and_then(|api_version| {
                 I32(self.correlation_id).into_protocol_bytes().and_then(|correlation_id| {
-                    self.client_id.into_protocol_bytes().map(|client_id| [api_key, api_version, correlation_id, client_id].concat())
+                    self.client_id.into_protocol_bytes().and_then(|client_id| {
+                        let tagged_fields = if flexible { ProtocolPrimitives::empty_tagged_fields().into_protocol_bytes() } else { Ok(vec![]) };
+                        tagged_fields.map(|tagged_fields| [api_key, api_version, correlation_id, client_id, tagged_fields].concat())
+                    })
                 })
             })
         })
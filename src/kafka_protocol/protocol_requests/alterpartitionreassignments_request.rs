@@ -0,0 +1,99 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+use crate::kafka_protocol::protocol_primitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 0
+///
+/// Must be sent to the controller.
+///
+#[derive(Clone, Debug)]
+pub struct AlterPartitionReassignmentsRequest {
+    pub timeout: i32,
+    pub topics: Vec<Topic>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Topic {
+    pub topic: String,
+    pub partitions: Vec<Partition>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Partition {
+    pub partition: i32,
+    /// the replicas to move the partition to, or None to cancel its ongoing reassignment
+    pub replicas: Option<Vec<i32>>,
+}
+
+impl KafkaApiVersioned for AlterPartitionReassignmentsRequest {
+    fn api_key() -> i16 {
+        45
+    }
+    fn version() -> i16 {
+        0
+    }
+    fn flexible() -> bool {
+        true
+    }
+}
+
+impl ProtocolSerializable for AlterPartitionReassignmentsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let timeout = self.timeout;
+        let topics = CompactArray(self.topics);
+        I32(timeout).into_protocol_bytes().and_then(|timeout| {
+            topics.into_protocol_bytes().and_then(|topics| {
+                ProtocolPrimitives::empty_tagged_fields().into_protocol_bytes().map(|tagged_fields| [timeout, topics, tagged_fields].concat())
+            })
+        })
+    }
+}
+
+impl ProtocolSerializable for Topic {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let topic = CompactString(self.topic);
+        let partitions = CompactArray(self.partitions);
+        topic.into_protocol_bytes().and_then(|topic| {
+            partitions.into_protocol_bytes().and_then(|partitions| {
+                ProtocolPrimitives::empty_tagged_fields().into_protocol_bytes().map(|tagged_fields| [topic, partitions, tagged_fields].concat())
+            })
+        })
+    }
+}
+
+impl ProtocolSerializable for Partition {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let partition = self.partition;
+        let replicas = CompactNullableArray(self.replicas.map(|replicas| replicas.into_iter().map(I32).collect::<Vec<ProtocolPrimitives>>()));
+        I32(partition).into_protocol_bytes().and_then(|partition| {
+            replicas.into_protocol_bytes().and_then(|replicas| {
+                ProtocolPrimitives::empty_tagged_fields().into_protocol_bytes().map(|tagged_fields| [partition, replicas, tagged_fields].concat())
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::alterpartitionreassignments_request::*;
+
+    #[test]
+    fn verify_alterpartitionreassignments_request() {
+        let request = AlterPartitionReassignmentsRequest {
+            timeout: 1000,
+            topics: vec![Topic {
+                topic: String::from("t"),
+                partitions: vec![Partition { partition: 0, replicas: Some(vec![1, 2]) }, Partition { partition: 1, replicas: None }],
+            }],
+        };
+        let expected: Vec<u8> = vec![
+            0, 0, 3, 232, // timeout
+            2, 2, 116, 3, // topics, topic, partitions
+            0, 0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 2, 0, // partition 0 moved to 1 and 2
+            0, 0, 0, 1, 0, 0, // partition 1 cancelled
+            0, 0, // topic and request tagged fields
+        ];
+        assert_eq!(expected, request.into_protocol_bytes().unwrap());
+    }
+}
@@ -0,0 +1,46 @@
+use crate::kafka_protocol::acl::AclBinding;
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+#[derive(Clone, Debug)]
+pub struct CreateAclsRequest {
+    pub creations: Vec<AclBinding>,
+}
+
+impl KafkaApiVersioned for CreateAclsRequest {
+    fn api_key() -> i16 {
+        30
+    }
+    fn version() -> i16 {
+        1
+    }
+}
+
+impl ProtocolSerializable for CreateAclsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        self.creations.into_protocol_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::acl::*;
+    use crate::kafka_protocol::protocol_requests::createacls_request::*;
+
+    proptest! {
+        #[test]
+        fn verify_serde_for_createacls_request(ref resource_name in ".*", ref principal in ".*") {
+            let request = CreateAclsRequest {
+                creations: vec![AclBinding {
+                    pattern: ResourcePattern { resource_type: AclResourceType::Group, resource_name: resource_name.clone(), pattern_type: AclPatternType::Literal },
+                    entry: AccessControlEntry { principal: principal.clone(), host: String::from("*"), operation: AclOperation::Read, permission_type: AclPermissionType::Allow },
+                }]
+            };
+            match request.into_protocol_bytes() {
+                Ok(_bytes) => (),
+                Err(e) => panic!("{}", e)
+            };
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+///
+/// Must be sent to the controller.
+///
+#[derive(Clone, Debug)]
+pub struct CreatePartitionsRequest {
+    pub topic_partitions: Vec<TopicPartitions>,
+    pub timeout: i32,
+    pub validate_only: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct TopicPartitions {
+    pub topic: String,
+    pub new_partitions: NewPartitions,
+}
+
+#[derive(Clone, Debug)]
+pub struct NewPartitions {
+    /// the new total number of partitions of the topic
+    pub count: i32,
+    /// the replicas of each new partition, or None for the broker to assign them
+    pub assignment: Option<Vec<Assignment>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Assignment {
+    pub broker_ids: Vec<i32>,
+}
+
+impl KafkaApiVersioned for CreatePartitionsRequest {
+    fn api_key() -> i16 {
+        37
+    }
+    fn version() -> i16 {
+        1
+    }
+}
+
+impl ProtocolSerializable for CreatePartitionsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let topic_partitions = self.topic_partitions;
+        let timeout = self.timeout;
+        let validate_only = self.validate_only;
+        topic_partitions.into_protocol_bytes().and_then(|topic_partitions| {
+            I32(timeout).into_protocol_bytes().and_then(|timeout| {
+                Boolean(validate_only).into_protocol_bytes().map(|validate_only| [topic_partitions, timeout, validate_only].concat())
+            })
+        })
+    }
+}
+
+impl ProtocolSerializable for TopicPartitions {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let topic = self.topic;
+        let new_partitions = self.new_partitions;
+        topic.into_protocol_bytes().and_then(|topic| new_partitions.into_protocol_bytes().map(|new_partitions| [topic, new_partitions].concat()))
+    }
+}
+
+impl ProtocolSerializable for NewPartitions {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let count = self.count;
+        let assignment = self.assignment;
+        I32(count).into_protocol_bytes().and_then(|count| assignment.into_protocol_bytes().map(|assignment| [count, assignment].concat()))
+    }
+}
+
+impl ProtocolSerializable for Assignment {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        self.broker_ids.into_iter().map(I32).collect::<Vec<ProtocolPrimitives>>().into_protocol_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::createpartitions_request::*;
+
+    #[test]
+    fn verify_createpartitions_request() {
+        let request = CreatePartitionsRequest {
+            topic_partitions: vec![
+                TopicPartitions {
+                    topic: String::from("a"),
+                    new_partitions: NewPartitions { count: 3, assignment: Some(vec![Assignment { broker_ids: vec![1, 2] }]) },
+                },
+                TopicPartitions { topic: String::from("b"), new_partitions: NewPartitions { count: 6, assignment: None } },
+            ],
+            timeout: 1000,
+            validate_only: true,
+        };
+        let expected: Vec<u8> = vec![
+            0, 0, 0, 2, // topic_partitions
+            0, 1, 97, 0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2, // a
+            0, 1, 98, 0, 0, 0, 6, 255, 255, 255, 255, // b
+            0, 0, 3, 232, // timeout
+            1,   // validate_only
+        ];
+        assert_eq!(expected, request.into_protocol_bytes().unwrap());
+    }
+
+    proptest! {
+        #[test]
+        fn verify_serde_for_createpartitions_request(ref topic in ".*", count in 1i32..1000) {
+            let request = CreatePartitionsRequest {
+                topic_partitions: vec![TopicPartitions { topic: topic.clone(), new_partitions: NewPartitions { count, assignment: None } }],
+                timeout: 42,
+                validate_only: false
+            };
+            match request.into_protocol_bytes() {
+                Ok(_bytes) => (),
+                Err(e) => panic!("{}", e)
+            };
+        }
+    }
+}
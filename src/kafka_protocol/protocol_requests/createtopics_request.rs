@@ -79,7 +79,7 @@ impl ProtocolSerializable for ReplicaAssignment {
         I32(self.partition).into_protocol_bytes().and_then(|partition| {
             self.replicas
                 .into_iter()
-                .map(|r| I32(r))
+                .map(I32)
                 .collect::<Vec<ProtocolPrimitives>>()
                 .into_protocol_bytes()
                 .map(|replicas| [partition, replicas].concat())
@@ -111,7 +111,7 @@ mod tests {
             };
             match request.into_protocol_bytes() {
                 Ok(_bytes) => (),
-                Err(e) => panic!(e)
+                Err(e) => panic!("{}", e)
             };
         }
     }
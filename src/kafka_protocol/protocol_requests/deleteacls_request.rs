@@ -0,0 +1,44 @@
+use crate::kafka_protocol::acl::AclBindingFilter;
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+///
+/// Deletes every ACL matching any of the filters.
+///
+#[derive(Clone, Debug)]
+pub struct DeleteAclsRequest {
+    pub filters: Vec<AclBindingFilter>,
+}
+
+impl KafkaApiVersioned for DeleteAclsRequest {
+    fn api_key() -> i16 {
+        31
+    }
+    fn version() -> i16 {
+        1
+    }
+}
+
+impl ProtocolSerializable for DeleteAclsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        self.filters.into_protocol_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::acl::*;
+    use crate::kafka_protocol::protocol_requests::deleteacls_request::*;
+
+    proptest! {
+        #[test]
+        fn verify_serde_for_deleteacls_request(ref principal in ".*") {
+            let request = DeleteAclsRequest { filters: vec![AclBindingFilter { principal: Some(principal.clone()), ..AclBindingFilter::any() }] };
+            match request.into_protocol_bytes() {
+                Ok(_bytes) => (),
+                Err(e) => panic!("{}", e)
+            };
+        }
+    }
+}
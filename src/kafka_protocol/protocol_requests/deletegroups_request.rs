@@ -0,0 +1,42 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+///
+/// Must be sent to each group's coordinator.
+///
+#[derive(Clone, Debug)]
+pub struct DeleteGroupsRequest {
+    pub groups_names: Vec<String>,
+}
+
+impl KafkaApiVersioned for DeleteGroupsRequest {
+    fn api_key() -> i16 {
+        42
+    }
+    fn version() -> i16 {
+        1
+    }
+}
+
+impl ProtocolSerializable for DeleteGroupsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        self.groups_names.into_protocol_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::deletegroups_request::*;
+
+    proptest! {
+        #[test]
+        fn verify_serde_for_deletegroups_request(ref group_a in ".*", ref group_b in ".*") {
+            let request = DeleteGroupsRequest { groups_names: vec![group_a.clone(), group_b.clone()] };
+            match request.into_protocol_bytes() {
+                Ok(_bytes) => (),
+                Err(e) => panic!("{}", e)
+            };
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+///
+/// Must be sent to the leader of each partition.
+///
+#[derive(Clone, Debug)]
+pub struct DeleteRecordsRequest {
+    pub topics: Vec<Topic>,
+    pub timeout: i32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Topic {
+    pub topic: String,
+    pub partitions: Vec<Partition>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Partition {
+    pub partition: i32,
+    /// records before this offset are deleted
+    pub offset: i64,
+}
+
+// deletes every record up to the partition's high watermark
+pub const HIGH_WATERMARK: i64 = -1;
+
+impl KafkaApiVersioned for DeleteRecordsRequest {
+    fn api_key() -> i16 {
+        21
+    }
+    fn version() -> i16 {
+        1
+    }
+}
+
+impl ProtocolSerializable for DeleteRecordsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let topics = self.topics;
+        let timeout = self.timeout;
+        topics.into_protocol_bytes().and_then(|topics| I32(timeout).into_protocol_bytes().map(|timeout| [topics, timeout].concat()))
+    }
+}
+
+impl ProtocolSerializable for Topic {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let topic = self.topic;
+        let partitions = self.partitions;
+        topic.into_protocol_bytes().and_then(|topic| partitions.into_protocol_bytes().map(|partitions| [topic, partitions].concat()))
+    }
+}
+
+impl ProtocolSerializable for Partition {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let partition = self.partition;
+        let offset = self.offset;
+        I32(partition).into_protocol_bytes().and_then(|partition| I64(offset).into_protocol_bytes().map(|offset| [partition, offset].concat()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::deleterecords_request::*;
+
+    proptest! {
+        #[test]
+        fn verify_serde_for_deleterecords_request(ref topic in ".*", partition in 0i32..1000, offset: i64) {
+            let request = DeleteRecordsRequest {
+                topics: vec![Topic { topic: topic.clone(), partitions: vec![Partition { partition, offset }] }],
+                timeout: 42
+            };
+            match request.into_protocol_bytes() {
+                Ok(_bytes) => (),
+                Err(e) => panic!("{}", e)
+            };
+        }
+    }
+}
@@ -39,7 +39,7 @@ mod tests {
             };
             match request.into_protocol_bytes() {
                 Ok(_bytes) => (),
-                Err(e) => panic!(e)
+                Err(e) => panic!("{}", e)
             };
         }
     }
@@ -0,0 +1,35 @@
+use crate::kafka_protocol::acl::AclBindingFilter;
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+#[derive(Clone, Debug)]
+pub struct DescribeAclsRequest {
+    pub filter: AclBindingFilter,
+}
+
+impl KafkaApiVersioned for DescribeAclsRequest {
+    fn api_key() -> i16 {
+        29
+    }
+    fn version() -> i16 {
+        1
+    }
+}
+
+impl ProtocolSerializable for DescribeAclsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        self.filter.into_protocol_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::describeacls_request::*;
+
+    #[test]
+    fn verify_describeacls_request() {
+        let request = DescribeAclsRequest { filter: AclBindingFilter::any() };
+        assert_eq!(vec![1, 255, 255, 1, 255, 255, 255, 255, 1, 1], request.into_protocol_bytes().unwrap());
+    }
+}
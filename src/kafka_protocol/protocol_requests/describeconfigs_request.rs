@@ -61,7 +61,7 @@ mod tests {
         let request = DescribeConfigsRequest { resources, include_synonyms: false };
         match request.into_protocol_bytes() {
             Ok(_bytes) => (),
-            Err(e) => panic!(e),
+            Err(e) => panic!("{}", e),
         }
     }
 
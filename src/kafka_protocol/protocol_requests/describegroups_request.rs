@@ -0,0 +1,75 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 4
+///
+/// Must be sent to each group's coordinator.
+///
+#[derive(Clone, Debug)]
+pub struct DescribeGroupsRequest {
+    pub groups: Vec<String>,
+    pub include_authorized_operations: bool,
+}
+
+impl KafkaApiVersioned for DescribeGroupsRequest {
+    fn api_key() -> i16 {
+        15
+    }
+    fn version() -> i16 {
+        4
+    }
+}
+
+impl ProtocolSerializable for DescribeGroupsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let groups = self.groups;
+        let include_authorized_operations = self.include_authorized_operations;
+        groups.into_protocol_bytes().and_then(|groups| {
+            ProtocolPrimitives::Boolean(include_authorized_operations)
+                .into_protocol_bytes()
+                .map(|include_authorized_operations| [groups, include_authorized_operations].concat())
+        })
+    }
+}
+
+/// Version 2, for brokers before Kafka 2.4, whose members have no
+/// group_instance_id
+///
+/// Must be sent to each group's coordinator.
+///
+#[derive(Clone, Debug)]
+pub struct LegacyDescribeGroupsRequest {
+    pub groups: Vec<String>,
+}
+
+impl KafkaApiVersioned for LegacyDescribeGroupsRequest {
+    fn api_key() -> i16 {
+        15
+    }
+    fn version() -> i16 {
+        2
+    }
+}
+
+impl ProtocolSerializable for LegacyDescribeGroupsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        self.groups.into_protocol_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::describegroups_request::*;
+
+    proptest! {
+        #[test]
+        fn verify_serde_for_describegroups_request(ref group_a in ".*", ref group_b in ".*") {
+            let request = DescribeGroupsRequest { groups: vec![group_a.clone(), group_b.clone()], include_authorized_operations: false };
+            match request.into_protocol_bytes() {
+                Ok(bytes) => assert_eq!(4 + 2 + group_a.len() + 2 + group_b.len() + 1, bytes.len()),
+                Err(e) => panic!("{}", e)
+            };
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+///
+/// Each broker only describes its own log directories.
+///
+#[derive(Clone, Debug)]
+pub struct DescribeLogDirsRequest {
+    /// None to describe every partition
+    pub topics: Option<Vec<Topic>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Topic {
+    pub topic: String,
+    pub partitions: Vec<i32>,
+}
+
+impl KafkaApiVersioned for DescribeLogDirsRequest {
+    fn api_key() -> i16 {
+        35
+    }
+    fn version() -> i16 {
+        1
+    }
+}
+
+impl ProtocolSerializable for DescribeLogDirsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        self.topics.into_protocol_bytes()
+    }
+}
+
+impl ProtocolSerializable for Topic {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let topic = self.topic;
+        let partitions = self.partitions;
+        topic.into_protocol_bytes().and_then(|topic| {
+            partitions.into_iter().map(I32).collect::<Vec<ProtocolPrimitives>>().into_protocol_bytes().map(|partitions| [topic, partitions].concat())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::describelogdirs_request::*;
+
+    #[test]
+    fn verify_describelogdirs_request() {
+        assert_eq!(vec![255, 255, 255, 255], DescribeLogDirsRequest { topics: None }.into_protocol_bytes().unwrap());
+        let request = DescribeLogDirsRequest { topics: Some(vec![Topic { topic: String::from("t"), partitions: vec![1] }]) };
+        assert_eq!(vec![0, 0, 0, 1, 0, 1, 116, 0, 0, 0, 1, 0, 0, 0, 1], request.into_protocol_bytes().unwrap());
+    }
+}
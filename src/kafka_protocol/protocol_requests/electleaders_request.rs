@@ -0,0 +1,79 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+///
+/// Must be sent to the controller.
+///
+#[derive(Clone, Debug)]
+pub struct ElectLeadersRequest {
+    pub election_type: i8,
+    /// None to elect leaders for every partition
+    pub topic_partitions: Option<Vec<TopicPartitions>>,
+    pub timeout: i32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TopicPartitions {
+    pub topic: String,
+    pub partitions: Vec<i32>,
+}
+
+pub enum ElectionType {
+    /// the first of a partition's replicas, when it is in sync
+    Preferred = 0,
+    /// any live replica, even if out of sync, when there is no in sync replica left
+    Unclean = 1,
+}
+
+impl KafkaApiVersioned for ElectLeadersRequest {
+    fn api_key() -> i16 {
+        43
+    }
+    fn version() -> i16 {
+        1
+    }
+}
+
+impl ProtocolSerializable for ElectLeadersRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let election_type = self.election_type;
+        let topic_partitions = self.topic_partitions;
+        let timeout = self.timeout;
+        I8(election_type).into_protocol_bytes().and_then(|election_type| {
+            topic_partitions
+                .into_protocol_bytes()
+                .and_then(|topic_partitions| I32(timeout).into_protocol_bytes().map(|timeout| [election_type, topic_partitions, timeout].concat()))
+        })
+    }
+}
+
+impl ProtocolSerializable for TopicPartitions {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let topic = self.topic;
+        let partitions = self.partitions;
+        topic.into_protocol_bytes().and_then(|topic| {
+            partitions.into_iter().map(I32).collect::<Vec<ProtocolPrimitives>>().into_protocol_bytes().map(|partitions| [topic, partitions].concat())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::electleaders_request::*;
+
+    #[test]
+    fn verify_electleaders_request() {
+        let request = ElectLeadersRequest {
+            election_type: ElectionType::Unclean as i8,
+            topic_partitions: Some(vec![TopicPartitions { topic: String::from("t"), partitions: vec![2] }]),
+            timeout: 1000,
+        };
+        assert_eq!(vec![1, 0, 0, 0, 1, 0, 1, 116, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 3, 232], request.into_protocol_bytes().unwrap());
+
+        let request = ElectLeadersRequest { election_type: ElectionType::Preferred as i8, topic_partitions: None, timeout: 1000 };
+        assert_eq!(vec![0, 255, 255, 255, 255, 0, 0, 3, 232], request.into_protocol_bytes().unwrap());
+    }
+}
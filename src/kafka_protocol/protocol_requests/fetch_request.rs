@@ -0,0 +1,137 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 5
+///
+#[derive(Clone, Debug)]
+pub struct FetchRequest {
+    pub replica_id: i32,
+    pub max_wait_time: i32,
+    pub min_bytes: i32,
+    pub max_bytes: i32,
+    pub isolation_level: i8,
+    pub topics: Vec<Topic>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Topic {
+    pub topic: String,
+    pub partitions: Vec<Partition>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Partition {
+    pub partition: i32,
+    pub fetch_offset: i64,
+    pub log_start_offset: i64,
+    pub partition_max_bytes: i32,
+}
+
+pub enum IsolationLevel {
+    ReadUncommitted = 0,
+    ReadCommitted = 1,
+}
+
+impl KafkaApiVersioned for FetchRequest {
+    fn api_key() -> i16 {
+        1
+    }
+    fn version() -> i16 {
+        5
+    }
+}
+
+impl ProtocolSerializable for FetchRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let replica_id = self.replica_id;
+        let max_wait_time = self.max_wait_time;
+        let min_bytes = self.min_bytes;
+        let max_bytes = self.max_bytes;
+        let isolation_level = self.isolation_level;
+        let topics = self.topics;
+        I32(replica_id).into_protocol_bytes().and_then(|replica_id| {
+            I32(max_wait_time).into_protocol_bytes().and_then(|max_wait_time| {
+                I32(min_bytes).into_protocol_bytes().and_then(|min_bytes| {
+                    I32(max_bytes).into_protocol_bytes().and_then(|max_bytes| {
+                        I8(isolation_level).into_protocol_bytes().and_then(|isolation_level| {
+                            topics
+                                .into_protocol_bytes()
+                                .map(|topics| [replica_id, max_wait_time, min_bytes, max_bytes, isolation_level, topics].concat())
+                        })
+                    })
+                })
+            })
+        })
+    }
+}
+
+impl ProtocolSerializable for Topic {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let topic = self.topic;
+        let partitions = self.partitions;
+        topic.into_protocol_bytes().and_then(|topic| partitions.into_protocol_bytes().map(|partitions| [topic, partitions].concat()))
+    }
+}
+
+impl ProtocolSerializable for Partition {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let partition = self.partition;
+        let fetch_offset = self.fetch_offset;
+        let log_start_offset = self.log_start_offset;
+        let partition_max_bytes = self.partition_max_bytes;
+        I32(partition).into_protocol_bytes().and_then(|partition| {
+            I64(fetch_offset).into_protocol_bytes().and_then(|fetch_offset| {
+                I64(log_start_offset).into_protocol_bytes().and_then(|log_start_offset| {
+                    I32(partition_max_bytes)
+                        .into_protocol_bytes()
+                        .map(|partition_max_bytes| [partition, fetch_offset, log_start_offset, partition_max_bytes].concat())
+                })
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::fetch_request::*;
+
+    #[test]
+    fn verify_fetch_request_with_empty_topic() {
+        let request = FetchRequest {
+            replica_id: -1,
+            max_wait_time: 500,
+            min_bytes: 1,
+            max_bytes: 1024 * 1024,
+            isolation_level: IsolationLevel::ReadCommitted as i8,
+            topics: vec![Topic {
+                topic: String::new(),
+                partitions: vec![Partition { partition: 0, fetch_offset: 0, log_start_offset: -1, partition_max_bytes: 1024 }],
+            }],
+        };
+        let bytes = request.into_protocol_bytes().unwrap();
+        assert_eq!(17 + 4 + 2 + 4 + 24, bytes.len());
+        assert_eq!([0, 0, 0, 1, 0, 0], bytes[17..23]);
+    }
+
+    proptest! {
+        #[test]
+        fn verify_serde_for_fetch_request(ref topic in ".*", fetch_offset in 0i64..) {
+            let request = FetchRequest {
+                replica_id: -1,
+                max_wait_time: 500,
+                min_bytes: 1,
+                max_bytes: 1024 * 1024,
+                isolation_level: IsolationLevel::ReadCommitted as i8,
+                topics: vec![Topic {
+                    topic: topic.clone(),
+                    partitions: vec![Partition { partition: 0, fetch_offset, log_start_offset: -1, partition_max_bytes: 1024 }]
+                }]
+            };
+            match request.into_protocol_bytes() {
+                Ok(bytes) => assert_eq!(17 + 4 + 2 + topic.len() + 4 + 24, bytes.len()),
+                Err(e) => panic!("{}", e)
+            };
+        }
+    }
+}
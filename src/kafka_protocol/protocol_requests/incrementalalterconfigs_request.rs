@@ -0,0 +1,111 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 0
+///
+/// Unlike AlterConfigs, only the given config entries of a resource are altered.
+/// Broker resources must be sent to that broker.
+///
+#[derive(Clone, Debug)]
+pub struct IncrementalAlterConfigsRequest {
+    pub resources: Vec<Resource>,
+    pub validate_only: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Resource {
+    pub resource_type: i8,
+    pub resource_name: String,
+    pub configs: Vec<AlterableConfig>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlterableConfig {
+    pub name: String,
+    pub config_operation: i8,
+    pub value: Option<String>,
+}
+
+pub enum ConfigOperation {
+    Set = 0,
+    /// reverts the config to its default
+    Delete = 1,
+    /// adds the value to a list config
+    Append = 2,
+    /// removes the value from a list config
+    Subtract = 3,
+}
+
+impl KafkaApiVersioned for IncrementalAlterConfigsRequest {
+    fn api_key() -> i16 {
+        44
+    }
+    fn version() -> i16 {
+        0
+    }
+}
+
+impl ProtocolSerializable for IncrementalAlterConfigsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let resources = self.resources;
+        let validate_only = self.validate_only;
+        resources.into_protocol_bytes().and_then(|resources| {
+            ProtocolPrimitives::Boolean(validate_only).into_protocol_bytes().map(|validate_only| [resources, validate_only].concat())
+        })
+    }
+}
+
+impl ProtocolSerializable for Resource {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let resource_type = self.resource_type;
+        let resource_name = self.resource_name;
+        let configs = self.configs;
+        ProtocolPrimitives::I8(resource_type).into_protocol_bytes().and_then(|resource_type| {
+            resource_name
+                .into_protocol_bytes()
+                .and_then(|resource_name| configs.into_protocol_bytes().map(|configs| [resource_type, resource_name, configs].concat()))
+        })
+    }
+}
+
+impl ProtocolSerializable for AlterableConfig {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let name = self.name;
+        let config_operation = self.config_operation;
+        let value = self.value;
+        name.into_protocol_bytes().and_then(|name| {
+            ProtocolPrimitives::I8(config_operation)
+                .into_protocol_bytes()
+                .and_then(|config_operation| value.into_protocol_bytes().map(|value| [name, config_operation, value].concat()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::incrementalalterconfigs_request::*;
+    use crate::kafka_protocol::protocol_requests::ResourceTypes;
+
+    #[test]
+    fn verify_incrementalalterconfigs_request() {
+        let request = IncrementalAlterConfigsRequest {
+            resources: vec![Resource {
+                resource_type: ResourceTypes::Topic as i8,
+                resource_name: String::from("t"),
+                configs: vec![
+                    AlterableConfig { name: String::from("a"), config_operation: ConfigOperation::Set as i8, value: Some(String::from("1")) },
+                    AlterableConfig { name: String::from("b"), config_operation: ConfigOperation::Delete as i8, value: None },
+                ],
+            }],
+            validate_only: false,
+        };
+        let expected: Vec<u8> = vec![
+            0, 0, 0, 1, 2, 0, 1, 116, 0, 0, 0, 2, // resources, topic t, configs
+            0, 1, 97, 0, 0, 1, 49, // set a to 1
+            0, 1, 98, 1, 255, 255, // delete b
+            0,   // validate_only
+        ];
+        assert_eq!(expected, request.into_protocol_bytes().unwrap());
+    }
+}
@@ -0,0 +1,66 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 4
+///
+#[derive(Clone, Debug)]
+pub struct ListGroupsRequest {
+    /// only list groups in these states, or all groups when empty
+    pub states_filter: Vec<String>,
+}
+
+impl KafkaApiVersioned for ListGroupsRequest {
+    fn api_key() -> i16 {
+        16
+    }
+    fn version() -> i16 {
+        4
+    }
+    fn flexible() -> bool {
+        true
+    }
+}
+
+impl ProtocolSerializable for ListGroupsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let states_filter = CompactArray(self.states_filter.into_iter().map(CompactString).collect());
+        states_filter.into_protocol_bytes().and_then(|states_filter| {
+            ProtocolPrimitives::empty_tagged_fields().into_protocol_bytes().map(|tagged_fields| [states_filter, tagged_fields].concat())
+        })
+    }
+}
+
+/// Version 2, for brokers before Kafka 2.6, which cannot filter groups by state
+///
+#[derive(Clone, Debug)]
+pub struct LegacyListGroupsRequest {}
+
+impl KafkaApiVersioned for LegacyListGroupsRequest {
+    fn api_key() -> i16 {
+        16
+    }
+    fn version() -> i16 {
+        2
+    }
+}
+
+impl ProtocolSerializable for LegacyListGroupsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::listgroups_request::*;
+
+    #[test]
+    fn verify_listgroups_request() {
+        assert_eq!(vec![1, 0], ListGroupsRequest { states_filter: vec![] }.into_protocol_bytes().unwrap());
+        assert_eq!(
+            vec![2, 6, 69, 109, 112, 116, 121, 0],
+            ListGroupsRequest { states_filter: vec![String::from("Empty")] }.into_protocol_bytes().unwrap()
+        );
+    }
+}
@@ -21,6 +21,10 @@ pub struct Partition {
     pub timestamp: i64,
 }
 
+// special timestamps querying for the log end and log start offset, respectively
+pub const LATEST_TIMESTAMP: i64 = -1;
+pub const EARLIEST_TIMESTAMP: i64 = -2;
+
 impl KafkaApiVersioned for ListOffsetsRequest {
     fn api_key() -> i16 {
         2
@@ -0,0 +1,70 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+use crate::kafka_protocol::protocol_primitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 0
+///
+/// Must be sent to the controller.
+///
+#[derive(Clone, Debug)]
+pub struct ListPartitionReassignmentsRequest {
+    pub timeout: i32,
+    /// None to list every ongoing reassignment
+    pub topics: Option<Vec<Topic>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Topic {
+    pub topic: String,
+    pub partitions: Vec<i32>,
+}
+
+impl KafkaApiVersioned for ListPartitionReassignmentsRequest {
+    fn api_key() -> i16 {
+        46
+    }
+    fn version() -> i16 {
+        0
+    }
+    fn flexible() -> bool {
+        true
+    }
+}
+
+impl ProtocolSerializable for ListPartitionReassignmentsRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let timeout = self.timeout;
+        let topics = CompactNullableArray(self.topics);
+        I32(timeout).into_protocol_bytes().and_then(|timeout| {
+            topics.into_protocol_bytes().and_then(|topics| {
+                ProtocolPrimitives::empty_tagged_fields().into_protocol_bytes().map(|tagged_fields| [timeout, topics, tagged_fields].concat())
+            })
+        })
+    }
+}
+
+impl ProtocolSerializable for Topic {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let topic = CompactString(self.topic);
+        let partitions = CompactArray(self.partitions.into_iter().map(I32).collect::<Vec<ProtocolPrimitives>>());
+        topic.into_protocol_bytes().and_then(|topic| {
+            partitions.into_protocol_bytes().and_then(|partitions| {
+                ProtocolPrimitives::empty_tagged_fields().into_protocol_bytes().map(|tagged_fields| [topic, partitions, tagged_fields].concat())
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::listpartitionreassignments_request::*;
+
+    #[test]
+    fn verify_listpartitionreassignments_request() {
+        assert_eq!(vec![0, 0, 3, 232, 0, 0], ListPartitionReassignmentsRequest { timeout: 1000, topics: None }.into_protocol_bytes().unwrap());
+        let request =
+            ListPartitionReassignmentsRequest { timeout: 1000, topics: Some(vec![Topic { topic: String::from("t"), partitions: vec![3] }]) };
+        assert_eq!(vec![0, 0, 3, 232, 2, 2, 116, 2, 0, 0, 0, 3, 0, 0], request.into_protocol_bytes().unwrap());
+    }
+}
@@ -43,7 +43,7 @@ mod tests {
         let metadata_request = MetadataRequest { topics, allow_auto_topic_creation: false };
 
         let request: Request<MetadataRequest> = Request {
-            header: RequestHeader { api_key: 3, api_version: 5, correlation_id: 42, client_id: String::from("sean") },
+            header: RequestHeader { api_key: 3, api_version: 5, correlation_id: 42, client_id: String::from("sean"), flexible: false },
             request_message: metadata_request,
         };
 
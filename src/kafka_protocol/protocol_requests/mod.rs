@@ -1,11 +1,29 @@
 pub mod alterconfigs_request;
+pub mod alterpartitionreassignments_request;
+pub mod createacls_request;
+pub mod createpartitions_request;
 pub mod createtopics_request;
+pub mod deleteacls_request;
+pub mod deletegroups_request;
+pub mod deleterecords_request;
 pub mod deletetopics_request;
+pub mod describeacls_request;
 pub mod describeconfigs_request;
+pub mod describegroups_request;
+pub mod describelogdirs_request;
+pub mod electleaders_request;
+pub mod fetch_request;
 pub mod findcoordinator_request;
+pub mod incrementalalterconfigs_request;
+pub mod listgroups_request;
 pub mod listoffsets_request;
+pub mod listpartitionreassignments_request;
 pub mod metadata_request;
+pub mod offsetcommit_request;
 pub mod offsetfetch_request;
+pub mod produce_request;
+pub mod saslauthenticate_request;
+pub mod saslhandshake_request;
 
 pub enum ResourceTypes {
     Unknown = 0,
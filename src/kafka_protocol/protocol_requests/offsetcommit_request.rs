@@ -0,0 +1,121 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 5
+///
+/// Must be sent to the group's coordinator.
+///
+#[derive(Clone, Debug)]
+pub struct OffsetCommitRequest {
+    pub group_id: String,
+    pub generation_id: i32,
+    pub member_id: String,
+    pub topics: Vec<Topic>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Topic {
+    pub topic: String,
+    pub partitions: Vec<Partition>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Partition {
+    pub partition: i32,
+    pub committed_offset: i64,
+    pub committed_metadata: Option<String>,
+}
+
+// commits from outside of the group (i.e. by an admin) do not belong to any generation or member
+pub const ADMIN_GENERATION_ID: i32 = -1;
+pub const ADMIN_MEMBER_ID: &str = "";
+
+impl OffsetCommitRequest {
+    /// A commit made by an admin rather than a member of the group,
+    /// which the coordinator only accepts when the group is empty.
+    pub fn admin(group_id: String, topics: Vec<Topic>) -> OffsetCommitRequest {
+        OffsetCommitRequest { group_id, generation_id: ADMIN_GENERATION_ID, member_id: String::from(ADMIN_MEMBER_ID), topics }
+    }
+}
+
+impl KafkaApiVersioned for OffsetCommitRequest {
+    fn api_key() -> i16 {
+        8
+    }
+    fn version() -> i16 {
+        5
+    }
+}
+
+impl ProtocolSerializable for OffsetCommitRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let group_id = self.group_id;
+        let generation_id = self.generation_id;
+        let member_id = self.member_id;
+        let topics = self.topics;
+        group_id.into_protocol_bytes().and_then(|group_id| {
+            I32(generation_id).into_protocol_bytes().and_then(|generation_id| {
+                member_id
+                    .into_protocol_bytes()
+                    .and_then(|member_id| topics.into_protocol_bytes().map(|topics| [group_id, generation_id, member_id, topics].concat()))
+            })
+        })
+    }
+}
+
+impl ProtocolSerializable for Topic {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let topic = self.topic;
+        let partitions = self.partitions;
+        topic.into_protocol_bytes().and_then(|topic| partitions.into_protocol_bytes().map(|partitions| [topic, partitions].concat()))
+    }
+}
+
+impl ProtocolSerializable for Partition {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let partition = self.partition;
+        let committed_offset = self.committed_offset;
+        let committed_metadata = self.committed_metadata;
+        I32(partition).into_protocol_bytes().and_then(|partition| {
+            I64(committed_offset).into_protocol_bytes().and_then(|committed_offset| {
+                committed_metadata.into_protocol_bytes().map(|committed_metadata| [partition, committed_offset, committed_metadata].concat())
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::offsetcommit_request::*;
+
+    #[test]
+    fn verify_admin_offsetcommit_request() {
+        let request = OffsetCommitRequest::admin(
+            String::from("g"),
+            vec![Topic { topic: String::from("t"), partitions: vec![Partition { partition: 1, committed_offset: 42, committed_metadata: None }] }],
+        );
+        let expected: Vec<u8> = vec![
+            0, 1, 103, // group_id
+            255, 255, 255, 255, // generation_id
+            0, 0, // member_id
+            0, 0, 0, 1, 0, 1, 116, // topic
+            0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 42, 255, 255, // partition
+        ];
+        assert_eq!(expected, request.into_protocol_bytes().unwrap());
+    }
+
+    proptest! {
+        #[test]
+        fn verify_serde_for_offsetcommit_request(ref topic in ".*", ref metadata in ".*") {
+            let request = OffsetCommitRequest::admin(
+                String::from("group"),
+                vec![Topic { topic: topic.clone(), partitions: vec![Partition { partition: 0, committed_offset: 0, committed_metadata: Some(metadata.clone()) }] }]
+            );
+            match request.into_protocol_bytes() {
+                Ok(_bytes) => (),
+                Err(e) => panic!("{}", e)
+            };
+        }
+    }
+}
@@ -38,12 +38,7 @@ impl ProtocolSerializable for Topic {
         let topic = self.topic;
         let partitions = self.partitions;
         topic.into_protocol_bytes().and_then(|topic| {
-            partitions
-                .into_iter()
-                .map(|p| I32(p))
-                .collect::<Vec<ProtocolPrimitives>>()
-                .into_protocol_bytes()
-                .map(|partitions| [topic, partitions].concat())
+            partitions.into_iter().map(I32).collect::<Vec<ProtocolPrimitives>>().into_protocol_bytes().map(|partitions| [topic, partitions].concat())
         })
     }
 }
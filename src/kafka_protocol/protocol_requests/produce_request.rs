@@ -0,0 +1,137 @@
+use std::io::{Error, ErrorKind};
+
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 3
+///
+/// Note: `Acks::None` is rejected when serializing, as the broker would not
+/// send a response for the client to read.
+///
+#[derive(Clone, Debug)]
+pub struct ProduceRequest {
+    pub transactional_id: Option<String>,
+    pub acks: i16,
+    pub timeout: i32,
+    pub topic_data: Vec<TopicData>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TopicData {
+    pub topic: String,
+    pub data: Vec<Data>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Data {
+    pub partition: i32,
+    pub record_set: Vec<u8>,
+}
+
+pub enum Acks {
+    /// not supported, as the client always waits for a response
+    None = 0,
+    Leader = 1,
+    All = -1,
+}
+
+impl KafkaApiVersioned for ProduceRequest {
+    fn api_key() -> i16 {
+        0
+    }
+    fn version() -> i16 {
+        3
+    }
+}
+
+impl ProtocolSerializable for ProduceRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        if self.acks == Acks::None as i16 {
+            return Err(Error::new(ErrorKind::InvalidInput, "Produce with acks=0 is not supported, as the broker sends no response"));
+        }
+        let transactional_id = self.transactional_id;
+        let acks = self.acks;
+        let timeout = self.timeout;
+        let topic_data = self.topic_data;
+        transactional_id.into_protocol_bytes().and_then(|transactional_id| {
+            I16(acks).into_protocol_bytes().and_then(|acks| {
+                I32(timeout)
+                    .into_protocol_bytes()
+                    .and_then(|timeout| topic_data.into_protocol_bytes().map(|topic_data| [transactional_id, acks, timeout, topic_data].concat()))
+            })
+        })
+    }
+}
+
+impl ProtocolSerializable for TopicData {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let topic = self.topic;
+        let data = self.data;
+        topic.into_protocol_bytes().and_then(|topic| data.into_protocol_bytes().map(|data| [topic, data].concat()))
+    }
+}
+
+impl ProtocolSerializable for Data {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        let partition = self.partition;
+        let record_set = self.record_set;
+        I32(partition)
+            .into_protocol_bytes()
+            .and_then(|partition| Bytes(record_set).into_protocol_bytes().map(|record_set| [partition, record_set].concat()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api_client::{ApiClient, ApiClientTrait, ApiRequestError};
+    use crate::kafka_protocol::protocol_request::Request;
+    use crate::kafka_protocol::protocol_requests::produce_request::*;
+    use crate::kafka_protocol::protocol_response::Response;
+    use crate::kafka_protocol::protocol_responses::produce_response::ProduceResponse;
+    use crate::KafkaServerAddr;
+
+    #[test]
+    fn verify_produce_request() {
+        let request = ProduceRequest {
+            transactional_id: None,
+            acks: Acks::All as i16,
+            timeout: 42,
+            topic_data: vec![TopicData { topic: String::from("foo"), data: vec![Data { partition: 1, record_set: vec![7, 8, 9] }] }],
+        };
+        let expected: Vec<u8> = vec![
+            255, 255, // null transactional_id
+            255, 255, // acks
+            0, 0, 0, 42, // timeout
+            0, 0, 0, 1, 0, 3, 102, 111, 111, // topic
+            0, 0, 0, 1, 0, 0, 0, 1, // partition
+            0, 0, 0, 3, 7, 8, 9, // record_set
+        ];
+        assert_eq!(expected, request.into_protocol_bytes().unwrap());
+    }
+
+    #[test]
+    fn verify_produce_request_without_acks() {
+        let request = ProduceRequest { transactional_id: None, acks: Acks::None as i16, timeout: 42, topic_data: vec![] };
+        // rejected before connecting, rather than waiting for a response which never comes
+        let server_addr = KafkaServerAddr::of(String::from("127.0.0.1"), 0, false);
+        let result: Result<Response<ProduceResponse>, ApiRequestError> = ApiClient::new().request(&server_addr, Request::of(request));
+        assert!(result.unwrap_err().error.contains("acks=0 is not supported"));
+    }
+
+    proptest! {
+        #[test]
+        fn verify_serde_for_produce_request(ref topic in ".*", ref transactional_id in ".*") {
+            let request = ProduceRequest {
+                transactional_id: Some(transactional_id.clone()),
+                acks: Acks::Leader as i16,
+                timeout: 42,
+                topic_data: vec![TopicData { topic: topic.clone(), data: vec![Data { partition: 0, record_set: vec![] }] }]
+            };
+            match request.into_protocol_bytes() {
+                Ok(_bytes) => (),
+                Err(e) => panic!("{}", e)
+            };
+        }
+    }
+}
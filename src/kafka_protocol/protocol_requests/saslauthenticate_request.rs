@@ -0,0 +1,44 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+///
+/// Carries the mechanism's authentication bytes, following a SaslHandshake
+/// on the same connection.
+///
+#[derive(Clone, Debug)]
+pub struct SaslAuthenticateRequest {
+    pub auth_bytes: Vec<u8>,
+}
+
+impl KafkaApiVersioned for SaslAuthenticateRequest {
+    fn api_key() -> i16 {
+        36
+    }
+    fn version() -> i16 {
+        1
+    }
+}
+
+impl ProtocolSerializable for SaslAuthenticateRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        Bytes(self.auth_bytes).into_protocol_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::saslauthenticate_request::*;
+
+    proptest! {
+        #[test]
+        fn verify_serde_for_saslauthenticate_request(ref auth_bytes in proptest::collection::vec(proptest::prelude::any::<u8>(), 0..64)) {
+            let request = SaslAuthenticateRequest { auth_bytes: auth_bytes.clone() };
+            match request.into_protocol_bytes() {
+                Ok(bytes) => assert_eq!(auth_bytes.len() + 4, bytes.len()),
+                Err(e) => panic!("{}", e)
+            };
+        }
+    }
+}
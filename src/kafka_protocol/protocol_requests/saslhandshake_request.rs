@@ -0,0 +1,38 @@
+use crate::kafka_protocol::api_verification::KafkaApiVersioned;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+///
+/// Must be the first request sent on a connection authenticated with SASL,
+/// followed by SaslAuthenticate requests.
+///
+#[derive(Clone, Debug)]
+pub struct SaslHandshakeRequest {
+    pub mechanism: String,
+}
+
+impl KafkaApiVersioned for SaslHandshakeRequest {
+    fn api_key() -> i16 {
+        17
+    }
+    fn version() -> i16 {
+        1
+    }
+}
+
+impl ProtocolSerializable for SaslHandshakeRequest {
+    fn into_protocol_bytes(self) -> ProtocolSerializeResult {
+        self.mechanism.into_protocol_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_requests::saslhandshake_request::*;
+
+    #[test]
+    fn verify_serde_for_saslhandshake_request() {
+        let request = SaslHandshakeRequest { mechanism: String::from("PLAIN") };
+        assert_eq!(vec![0, 5, 80, 76, 65, 73, 78], request.into_protocol_bytes().unwrap());
+    }
+}
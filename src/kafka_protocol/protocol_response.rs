@@ -33,3 +33,15 @@ impl ProtocolDeserializable<ResponseHeader> for Vec<u8> {
         de_i32(self.as_slice()).map(|correlation_id| ResponseHeader { correlation_id })
     }
 }
+
+/// Flexible API versions follow the response header's correlation_id with
+/// tagged fields, which are removed so that `Response` can be deserialized
+/// the same way for every API version.
+///
+pub fn without_header_tagged_fields(bytes: Vec<u8>) -> ProtocolDeserializeResult<Vec<u8>> {
+    bytes
+        .get(4..)
+        .ok_or_else(|| DeserializeError::of("Response too small for header"))
+        .and_then(de_tagged_fields)
+        .map(|(_, remaining_bytes)| [&bytes[0..4], remaining_bytes].concat())
+}
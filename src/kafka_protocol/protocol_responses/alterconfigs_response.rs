@@ -24,7 +24,7 @@ impl ProtocolDeserializable<AlterConfigsResponse> for Vec<u8> {
     }
 }
 
-fn deserialize_resource(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<Resource>> {
+fn deserialize_resource(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Resource>> {
     de_i16(&bytes[0..=1]).and_then(|error_code| {
         de_string(&bytes[2..]).and_then(|(error_message, bytes)| {
             Ok((bytes[0] as i8, &bytes[1..])).and_then(|(resource_type, bytes)| {
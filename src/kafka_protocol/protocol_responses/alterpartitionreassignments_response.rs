@@ -0,0 +1,59 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 0
+#[derive(Debug)]
+pub struct AlterPartitionReassignmentsResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    pub error_message: Option<String>,
+    pub responses: Vec<Response>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub topic: String,
+    pub partitions: Vec<PartitionResponse>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartitionResponse {
+    pub partition: i32,
+    pub error_code: i16,
+    pub error_message: Option<String>,
+}
+
+impl ProtocolDeserializable<AlterPartitionReassignmentsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<AlterPartitionReassignmentsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_i16(&self[4..=5]).and_then(|error_code| {
+                de_compact_string(&self[6..]).and_then(|(error_message, bytes)| {
+                    de_compact_array(bytes, deserialize_response).map(|(responses, _bytes)| AlterPartitionReassignmentsResponse {
+                        throttle_time_ms,
+                        error_code,
+                        error_message,
+                        responses,
+                    })
+                })
+            })
+        })
+    }
+}
+
+fn deserialize_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Response>> {
+    de_compact_string(bytes).and_then(|(topic, bytes)| {
+        topic.ok_or_else(|| DeserializeError::of("Expected topic name")).and_then(|topic| {
+            de_compact_array(bytes, deserialize_partition_response)
+                .and_then(|(partitions, bytes)| de_tagged_fields(bytes).map(|(_, bytes)| (Response { topic, partitions }, bytes)))
+        })
+    })
+}
+
+fn deserialize_partition_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, PartitionResponse>> {
+    de_i32(&bytes[0..=3]).and_then(|partition| {
+        de_i16(&bytes[4..=5]).and_then(|error_code| {
+            de_compact_string(&bytes[6..]).and_then(|(error_message, bytes)| {
+                de_tagged_fields(bytes).map(|(_, bytes)| (PartitionResponse { partition, error_code, error_message }, bytes))
+            })
+        })
+    })
+}
@@ -0,0 +1,28 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+#[derive(Debug)]
+pub struct CreateAclsResponse {
+    pub throttle_time_ms: i32,
+    /// in the same order as the creations of the request
+    pub results: Vec<CreationResult>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreationResult {
+    pub error_code: i16,
+    pub error_message: Option<String>,
+}
+
+impl ProtocolDeserializable<CreateAclsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<CreateAclsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_array(&self[4..], deserialize_creation_result).map(|(results, _bytes)| CreateAclsResponse { throttle_time_ms, results })
+        })
+    }
+}
+
+fn deserialize_creation_result(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, CreationResult>> {
+    de_i16(&bytes[0..=1])
+        .and_then(|error_code| de_string(&bytes[2..]).map(|(error_message, bytes)| (CreationResult { error_code, error_message }, bytes)))
+}
@@ -0,0 +1,60 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+#[derive(Debug)]
+pub struct CreatePartitionsResponse {
+    pub throttle_time_ms: i32,
+    pub topic_errors: Vec<TopicError>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TopicError {
+    pub topic: String,
+    pub error_code: i16,
+    pub error_message: Option<String>,
+}
+
+impl Display for TopicError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Topic Error for {}: {} {}", self.topic, self.error_code, self.error_message.as_deref().unwrap_or(""))
+    }
+}
+
+impl ProtocolDeserializable<CreatePartitionsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<CreatePartitionsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_array(&self[4..], deserialize_topic_error).map(|(topic_errors, _bytes)| CreatePartitionsResponse { throttle_time_ms, topic_errors })
+        })
+    }
+}
+
+fn deserialize_topic_error(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, TopicError>> {
+    de_string(bytes).and_then(|(topic, bytes)| {
+        topic.ok_or_else(|| DeserializeError::of("Expected topic name")).and_then(|topic| {
+            de_i16(&bytes[0..=1])
+                .and_then(|error_code| de_string(&bytes[2..]).map(|(error_message, bytes)| (TopicError { topic, error_code, error_message }, bytes)))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_createpartitions_response() {
+        let bytes: Vec<u8> = vec![0, 0, 0, 0, 0, 0, 0, 2, 0, 1, 97, 0, 0, 255, 255, 0, 1, 98, 0, 37, 0, 2, 110, 111];
+        let response: CreatePartitionsResponse = bytes.into_protocol_type().unwrap();
+        assert_eq!(
+            vec![
+                TopicError { topic: String::from("a"), error_code: 0, error_message: None },
+                TopicError { topic: String::from("b"), error_code: 37, error_message: Some(String::from("no")) },
+            ],
+            response.topic_errors
+        );
+    }
+}
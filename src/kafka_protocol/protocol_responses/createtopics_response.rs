@@ -35,8 +35,8 @@ impl ProtocolDeserializable<CreateTopicsResponse> for Vec<u8> {
     }
 }
 
-fn deserialize_topic_errors(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<TopicError>> {
-    de_string(&bytes).and_then(|(topic, remaining_bytes)| {
+fn deserialize_topic_errors(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, TopicError>> {
+    de_string(bytes).and_then(|(topic, remaining_bytes)| {
         let topic = topic.expect("Unexpected missing topic name in TopicError");
         de_i16(&remaining_bytes[0..2]).and_then(|error_code| {
             de_string(&remaining_bytes[2..])
@@ -0,0 +1,47 @@
+use crate::kafka_protocol::acl::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+#[derive(Debug)]
+pub struct DeleteAclsResponse {
+    pub throttle_time_ms: i32,
+    /// in the same order as the filters of the request
+    pub filter_results: Vec<FilterResult>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FilterResult {
+    pub error_code: i16,
+    pub error_message: Option<String>,
+    pub matching_acls: Vec<MatchingAcl>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchingAcl {
+    pub error_code: i16,
+    pub error_message: Option<String>,
+    pub acl: AclBinding,
+}
+
+impl ProtocolDeserializable<DeleteAclsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<DeleteAclsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_array(&self[4..], deserialize_filter_result).map(|(filter_results, _bytes)| DeleteAclsResponse { throttle_time_ms, filter_results })
+        })
+    }
+}
+
+fn deserialize_filter_result(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, FilterResult>> {
+    de_i16(&bytes[0..=1]).and_then(|error_code| {
+        de_string(&bytes[2..]).and_then(|(error_message, bytes)| {
+            de_array(bytes, deserialize_matching_acl).map(|(matching_acls, bytes)| (FilterResult { error_code, error_message, matching_acls }, bytes))
+        })
+    })
+}
+
+fn deserialize_matching_acl(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, MatchingAcl>> {
+    de_i16(&bytes[0..=1]).and_then(|error_code| {
+        de_string(&bytes[2..])
+            .and_then(|(error_message, bytes)| de_acl_binding(bytes).map(|(acl, bytes)| (MatchingAcl { error_code, error_message, acl }, bytes)))
+    })
+}
@@ -0,0 +1,48 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+#[derive(Debug)]
+pub struct DeleteGroupsResponse {
+    pub throttle_time_ms: i32,
+    pub results: Vec<DeletableGroupResult>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeletableGroupResult {
+    pub group_id: String,
+    pub error_code: i16,
+}
+
+impl ProtocolDeserializable<DeleteGroupsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<DeleteGroupsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_array(&self[4..], deserialize_result).map(|(results, _bytes)| DeleteGroupsResponse { throttle_time_ms, results })
+        })
+    }
+}
+
+fn deserialize_result(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, DeletableGroupResult>> {
+    de_string(bytes).and_then(|(group_id, bytes)| {
+        group_id
+            .ok_or_else(|| DeserializeError::of("Expected group_id"))
+            .and_then(|group_id| de_i16(&bytes[0..=1]).map(|error_code| (DeletableGroupResult { group_id, error_code }, &bytes[2..])))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_deletegroups_response() {
+        let bytes: Vec<u8> = vec![0, 0, 0, 0, 0, 0, 0, 2, 0, 1, 97, 0, 0, 0, 1, 98, 0, 68];
+        let response: DeleteGroupsResponse = bytes.into_protocol_type().unwrap();
+        assert_eq!(
+            vec![
+                DeletableGroupResult { group_id: String::from("a"), error_code: 0 },
+                DeletableGroupResult { group_id: String::from("b"), error_code: 68 },
+            ],
+            response.results
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+#[derive(Debug)]
+pub struct DeleteRecordsResponse {
+    pub throttle_time_ms: i32,
+    pub topics: Vec<Topic>,
+}
+
+#[derive(Debug)]
+pub struct Topic {
+    pub topic: String,
+    pub partitions: Vec<Partition>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Partition {
+    pub partition: i32,
+    pub low_watermark: i64,
+    pub error_code: i16,
+}
+
+impl ProtocolDeserializable<DeleteRecordsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<DeleteRecordsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_array(&self[4..], deserialize_topic).map(|(topics, _bytes)| DeleteRecordsResponse { throttle_time_ms, topics })
+        })
+    }
+}
+
+fn deserialize_topic(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Topic>> {
+    de_string(bytes).and_then(|(topic, bytes)| {
+        topic
+            .ok_or_else(|| DeserializeError::of("Expected topic name"))
+            .and_then(|topic| de_array(bytes, deserialize_partition).map(|(partitions, bytes)| (Topic { topic, partitions }, bytes)))
+    })
+}
+
+fn deserialize_partition(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Partition>> {
+    de_i32(&bytes[0..=3]).and_then(|partition| {
+        de_i64(&bytes[4..=11])
+            .and_then(|low_watermark| de_i16(&bytes[12..=13]).map(|error_code| (Partition { partition, low_watermark, error_code }, &bytes[14..])))
+    })
+}
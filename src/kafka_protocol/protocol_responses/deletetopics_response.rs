@@ -38,7 +38,7 @@ impl ProtocolDeserializable<DeleteTopicsResponse> for Vec<u8> {
     }
 }
 
-fn deserialize_topic_error_codes(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<TopicErrorCode>> {
+fn deserialize_topic_error_codes(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, TopicErrorCode>> {
     de_string(bytes).and_then(|(topic, remaining_bytes)| {
         let topic = topic.expect("Unexpected missing topic name in TopicErrorCode");
         de_i16(&remaining_bytes[0..2]).map(|error_code| (TopicErrorCode { topic, error_code }, &remaining_bytes[2..]))
@@ -0,0 +1,76 @@
+use crate::kafka_protocol::acl::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+#[derive(Debug)]
+pub struct DescribeAclsResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    pub error_message: Option<String>,
+    pub resources: Vec<Resource>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Resource {
+    pub pattern: ResourcePattern,
+    pub acls: Vec<AccessControlEntry>,
+}
+
+impl DescribeAclsResponse {
+    pub fn acl_bindings(&self) -> Vec<AclBinding> {
+        self.resources
+            .iter()
+            .flat_map(|resource| resource.acls.iter().map(move |entry| AclBinding { pattern: resource.pattern.clone(), entry: entry.clone() }))
+            .collect()
+    }
+}
+
+impl ProtocolDeserializable<DescribeAclsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<DescribeAclsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_i16(&self[4..=5]).and_then(|error_code| {
+                de_string(&self[6..]).and_then(|(error_message, bytes)| {
+                    de_array(bytes, deserialize_resource).map(|(resources, _bytes)| DescribeAclsResponse {
+                        throttle_time_ms,
+                        error_code,
+                        error_message,
+                        resources,
+                    })
+                })
+            })
+        })
+    }
+}
+
+fn deserialize_resource(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Resource>> {
+    de_resource_pattern(bytes)
+        .and_then(|(pattern, bytes)| de_array(bytes, de_access_control_entry).map(|(acls, bytes)| (Resource { pattern, acls }, bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_describeacls_response() {
+        let bytes: Vec<u8> = vec![
+            0, 0, 0, 0, 0, 0, 255, 255, 0, 0, 0, 1, // throttle_time_ms, error_code, error_message, resources
+            2, 0, 1, 116, 3, 0, 0, 0, 2, // topic t, literal, acls
+            0, 6, 85, 115, 101, 114, 58, 97, 0, 1, 42, 3, 3, // User:a * read allow
+            0, 6, 85, 115, 101, 114, 58, 98, 0, 1, 42, 4, 2, // User:b * write deny
+        ];
+        let response: DescribeAclsResponse = bytes.into_protocol_type().unwrap();
+        let bindings = response.acl_bindings();
+        assert_eq!(2, bindings.len());
+        assert_eq!(AclResourceType::Topic, bindings[1].pattern.resource_type);
+        assert_eq!(
+            AccessControlEntry {
+                principal: String::from("User:b"),
+                host: String::from("*"),
+                operation: AclOperation::Write,
+                permission_type: AclPermissionType::Deny
+            },
+            bindings[1].entry
+        );
+    }
+}
@@ -54,11 +54,11 @@ impl ProtocolDeserializable<DescribeConfigsResponse> for Vec<u8> {
     }
 }
 
-fn deserialize_resource(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<Resource>> {
+fn deserialize_resource(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Resource>> {
     de_i16(&bytes[0..=1]).and_then(|error_code| {
         de_string(&bytes[2..]).and_then(|(error_message, bytes)| {
             Ok((bytes[0] as i8, &bytes[1..])).and_then(|(resource_type, bytes)| {
-                de_string(&bytes).and_then(|(resource_name, bytes)| {
+                de_string(bytes).and_then(|(resource_name, bytes)| {
                     de_array(bytes, deserialize_config_entry).map(|(config_entries, bytes)| {
                         (
                             Resource {
@@ -77,9 +77,9 @@ fn deserialize_resource(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<R
     })
 }
 
-fn deserialize_config_entry(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<ConfigEntry>> {
-    de_string(&bytes).and_then(|(config_name, bytes)| {
-        de_string(&bytes).and_then(|(config_value, bytes)| {
+fn deserialize_config_entry(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, ConfigEntry>> {
+    de_string(bytes).and_then(|(config_name, bytes)| {
+        de_string(bytes).and_then(|(config_value, bytes)| {
             let read_only = bytes[0] == 1;
             let config_source = bytes[1] as i8;
             let is_sensitive = bytes[2] == 1;
@@ -102,9 +102,9 @@ fn deserialize_config_entry(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSi
     })
 }
 
-fn deserialize_config_synonym(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<ConfigSynonym>> {
-    de_string(&bytes).and_then(|(config_name, bytes)| {
-        de_string(&bytes).map(|(config_value, bytes)| {
+fn deserialize_config_synonym(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, ConfigSynonym>> {
+    de_string(bytes).and_then(|(config_name, bytes)| {
+        de_string(bytes).map(|(config_value, bytes)| {
             (
                 ConfigSynonym { config_name: config_name.expect("Unexpected undefined config name"), config_value, config_source: bytes[0] as i8 },
                 &bytes[1..],
@@ -0,0 +1,223 @@
+use crate::kafka_protocol::consumer_protocol::*;
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 4
+#[derive(Debug, Clone)]
+pub struct DescribeGroupsResponse {
+    pub throttle_time_ms: i32,
+    pub groups: Vec<Group>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Group {
+    pub error_code: i16,
+    pub group_id: String,
+    pub group_state: String,
+    pub protocol_type: String,
+    /// the assignor, for groups using the consumer protocol
+    pub protocol_data: String,
+    pub members: Vec<Member>,
+    /// i32::MIN when not requested or described by a broker before Kafka 2.4
+    pub authorized_operations: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub member_id: String,
+    /// always None when described by a broker before Kafka 2.4
+    pub group_instance_id: Option<String>,
+    pub client_id: String,
+    pub client_host: String,
+    pub member_metadata: Vec<u8>,
+    pub member_assignment: Vec<u8>,
+}
+
+impl Group {
+    /// Decodes the metadata and assignment of each member, which is only
+    /// possible for groups using the consumer protocol.
+    pub fn consumer_members(&self) -> ProtocolDeserializeResult<Vec<ConsumerMember>> {
+        if self.protocol_type != CONSUMER_PROTOCOL_TYPE {
+            return Err(DeserializeError::of(&format!("Group {} uses the {} protocol, not consumer", self.group_id, self.protocol_type)));
+        }
+        self.members
+            .iter()
+            .map(|member| {
+                member.member_metadata.clone().into_protocol_type().and_then(|subscription: ConsumerSubscription| {
+                    member.member_assignment.clone().into_protocol_type().map(|assignment: ConsumerAssignment| ConsumerMember {
+                        member_id: member.member_id.clone(),
+                        client_id: member.client_id.clone(),
+                        client_host: member.client_host.clone(),
+                        subscription,
+                        assignment,
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+impl ProtocolDeserializable<DescribeGroupsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<DescribeGroupsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_array(&self[4..], |bytes| deserialize_group(bytes, 4)).map(|(groups, _bytes)| DescribeGroupsResponse { throttle_time_ms, groups })
+        })
+    }
+}
+
+/// Version 2, whose groups have no authorized_operations and whose members
+/// have no group_instance_id
+#[derive(Debug, Clone)]
+pub struct LegacyDescribeGroupsResponse {
+    pub throttle_time_ms: i32,
+    pub groups: Vec<Group>,
+}
+
+impl ProtocolDeserializable<LegacyDescribeGroupsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<LegacyDescribeGroupsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_array(&self[4..], |bytes| deserialize_group(bytes, 2))
+                .map(|(groups, _bytes)| LegacyDescribeGroupsResponse { throttle_time_ms, groups })
+        })
+    }
+}
+
+fn deserialize_group(bytes: &[u8], version: i16) -> ProtocolDeserializeResult<DynamicSize<'_, Group>> {
+    de_i16(&bytes[0..=1]).and_then(|error_code| {
+        de_string(&bytes[2..]).and_then(|(group_id, bytes)| {
+            de_string(bytes).and_then(|(group_state, bytes)| {
+                de_string(bytes).and_then(|(protocol_type, bytes)| {
+                    de_string(bytes).and_then(|(protocol_data, bytes)| {
+                        de_array(bytes, |bytes| deserialize_member(bytes, version)).and_then(|(members, bytes)| {
+                            let authorized_operations = match version {
+                                2 => Ok((i32::MIN, bytes)),
+                                _ => de_i32(&bytes[0..=3]).map(|authorized_operations| (authorized_operations, &bytes[4..])),
+                            };
+                            authorized_operations.map(|(authorized_operations, bytes)| {
+                                let group = Group {
+                                    error_code,
+                                    group_id: group_id.unwrap_or_default(),
+                                    group_state: group_state.unwrap_or_default(),
+                                    protocol_type: protocol_type.unwrap_or_default(),
+                                    protocol_data: protocol_data.unwrap_or_default(),
+                                    members,
+                                    authorized_operations,
+                                };
+                                (group, bytes)
+                            })
+                        })
+                    })
+                })
+            })
+        })
+    })
+}
+
+fn deserialize_member(bytes: &[u8], version: i16) -> ProtocolDeserializeResult<DynamicSize<'_, Member>> {
+    de_string(bytes).and_then(|(member_id, bytes)| {
+        let group_instance_id = match version {
+            2 => Ok((None, bytes)),
+            _ => de_string(bytes),
+        };
+        group_instance_id.and_then(|(group_instance_id, bytes)| {
+            de_string(bytes).and_then(|(client_id, bytes)| {
+                de_string(bytes).and_then(|(client_host, bytes)| {
+                    de_bytes(bytes).and_then(|(member_metadata, bytes)| {
+                        de_bytes(bytes).map(|(member_assignment, bytes)| {
+                            let member = Member {
+                                member_id: member_id.unwrap_or_default(),
+                                group_instance_id,
+                                client_id: client_id.unwrap_or_default(),
+                                client_host: client_host.unwrap_or_default(),
+                                member_metadata: member_metadata.unwrap_or_default(),
+                                member_assignment: member_assignment.unwrap_or_default(),
+                            };
+                            (member, bytes)
+                        })
+                    })
+                })
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+    use crate::kafka_protocol::protocol_serializable::ProtocolSerializable;
+
+    use super::*;
+
+    #[test]
+    fn verify_describegroups_response() {
+        let subscription =
+            [I16(0).into_protocol_bytes().unwrap(), vec![String::from("foo")].into_protocol_bytes().unwrap(), vec![255, 255, 255, 255]].concat();
+        let assignment = [
+            I16(0).into_protocol_bytes().unwrap(),
+            vec![0, 0, 0, 1],
+            String::from("foo").into_protocol_bytes().unwrap(),
+            vec![I32(0), I32(2)].into_protocol_bytes().unwrap(),
+            vec![255, 255, 255, 255],
+        ]
+        .concat();
+        let member = [
+            String::from("member-1").into_protocol_bytes().unwrap(),
+            vec![255, 255], // null group_instance_id
+            String::from("client").into_protocol_bytes().unwrap(),
+            String::from("/127.0.0.1").into_protocol_bytes().unwrap(),
+            Bytes(subscription).into_protocol_bytes().unwrap(),
+            Bytes(assignment).into_protocol_bytes().unwrap(),
+        ]
+        .concat();
+        let bytes = [
+            vec![0, 0, 0, 0], // throttle_time_ms
+            vec![0, 0, 0, 1, 0, 0],
+            String::from("group").into_protocol_bytes().unwrap(),
+            String::from("Stable").into_protocol_bytes().unwrap(),
+            String::from("consumer").into_protocol_bytes().unwrap(),
+            String::from("range").into_protocol_bytes().unwrap(),
+            vec![0, 0, 0, 1],
+            member,
+            vec![0x80, 0, 0, 0], // authorized_operations
+        ]
+        .concat();
+
+        let response: DescribeGroupsResponse = bytes.into_protocol_type().unwrap();
+        let group = &response.groups[0];
+        assert_eq!(("group", "Stable", "range"), (group.group_id.as_str(), group.group_state.as_str(), group.protocol_data.as_str()));
+        assert_eq!(None, group.members[0].group_instance_id);
+
+        let consumer_members = group.consumer_members().unwrap();
+        assert_eq!("member-1", consumer_members[0].member_id);
+        assert_eq!(vec![String::from("foo")], consumer_members[0].subscription.topics);
+        assert_eq!(vec![TopicPartitions { topic: String::from("foo"), partitions: vec![0, 2] }], consumer_members[0].assignment.assigned_partitions);
+    }
+
+    #[test]
+    fn verify_consumer_members_of_rebalancing_group() {
+        let member = [
+            String::from("member-1").into_protocol_bytes().unwrap(),
+            vec![255, 255], // null group_instance_id
+            String::from("client").into_protocol_bytes().unwrap(),
+            String::from("/127.0.0.1").into_protocol_bytes().unwrap(),
+            vec![0, 0, 0, 0], // empty member_metadata
+            vec![0, 0, 0, 0], // empty member_assignment
+        ]
+        .concat();
+        let bytes = [
+            vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 0],
+            String::from("group").into_protocol_bytes().unwrap(),
+            String::from("PreparingRebalance").into_protocol_bytes().unwrap(),
+            String::from("consumer").into_protocol_bytes().unwrap(),
+            String::from("range").into_protocol_bytes().unwrap(),
+            vec![0, 0, 0, 1],
+            member,
+            vec![0x80, 0, 0, 0],
+        ]
+        .concat();
+
+        let response: DescribeGroupsResponse = bytes.into_protocol_type().unwrap();
+        let consumer_members = response.groups[0].consumer_members().unwrap();
+        assert_eq!(ConsumerSubscription::default(), consumer_members[0].subscription);
+        assert_eq!(ConsumerAssignment::default(), consumer_members[0].assignment);
+    }
+}
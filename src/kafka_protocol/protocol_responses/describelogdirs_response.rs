@@ -0,0 +1,66 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+#[derive(Debug)]
+pub struct DescribeLogDirsResponse {
+    pub throttle_time_ms: i32,
+    pub results: Vec<LogDir>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogDir {
+    pub error_code: i16,
+    pub log_dir: String,
+    pub topics: Vec<Topic>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Topic {
+    pub topic: String,
+    pub partitions: Vec<Partition>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Partition {
+    pub partition: i32,
+    pub partition_size: i64,
+    /// how far the replica is behind the log end offset (or, for a future replica, the current replica)
+    pub offset_lag: i64,
+    /// whether the replica is being moved to this log directory
+    pub is_future_key: bool,
+}
+
+impl ProtocolDeserializable<DescribeLogDirsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<DescribeLogDirsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_array(&self[4..], deserialize_log_dir).map(|(results, _bytes)| DescribeLogDirsResponse { throttle_time_ms, results })
+        })
+    }
+}
+
+fn deserialize_log_dir(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, LogDir>> {
+    de_i16(&bytes[0..=1]).and_then(|error_code| {
+        de_string(&bytes[2..]).and_then(|(log_dir, bytes)| {
+            log_dir
+                .ok_or_else(|| DeserializeError::of("Expected log_dir"))
+                .and_then(|log_dir| de_array(bytes, deserialize_topic).map(|(topics, bytes)| (LogDir { error_code, log_dir, topics }, bytes)))
+        })
+    })
+}
+
+fn deserialize_topic(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Topic>> {
+    de_string(bytes).and_then(|(topic, bytes)| {
+        topic
+            .ok_or_else(|| DeserializeError::of("Expected topic name"))
+            .and_then(|topic| de_array(bytes, deserialize_partition).map(|(partitions, bytes)| (Topic { topic, partitions }, bytes)))
+    })
+}
+
+fn deserialize_partition(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Partition>> {
+    de_i32(&bytes[0..=3]).and_then(|partition| {
+        de_i64(&bytes[4..=11]).and_then(|partition_size| {
+            de_i64(&bytes[12..=19])
+                .map(|offset_lag| (Partition { partition, partition_size, offset_lag, is_future_key: bytes[20] != 0 }, &bytes[21..]))
+        })
+    })
+}
@@ -0,0 +1,53 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+#[derive(Debug)]
+pub struct ElectLeadersResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    pub replica_election_results: Vec<ReplicaElectionResult>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplicaElectionResult {
+    pub topic: String,
+    pub partition_results: Vec<PartitionResult>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartitionResult {
+    pub partition: i32,
+    pub error_code: i16,
+    pub error_message: Option<String>,
+}
+
+impl ProtocolDeserializable<ElectLeadersResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<ElectLeadersResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_i16(&self[4..=5]).and_then(|error_code| {
+                de_array(&self[6..], deserialize_replica_election_result).map(|(replica_election_results, _bytes)| ElectLeadersResponse {
+                    throttle_time_ms,
+                    error_code,
+                    replica_election_results,
+                })
+            })
+        })
+    }
+}
+
+fn deserialize_replica_election_result(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, ReplicaElectionResult>> {
+    de_string(bytes).and_then(|(topic, bytes)| {
+        topic.ok_or_else(|| DeserializeError::of("Expected topic name")).and_then(|topic| {
+            de_array(bytes, deserialize_partition_result)
+                .map(|(partition_results, bytes)| (ReplicaElectionResult { topic, partition_results }, bytes))
+        })
+    })
+}
+
+fn deserialize_partition_result(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, PartitionResult>> {
+    de_i32(&bytes[0..=3]).and_then(|partition| {
+        de_i16(&bytes[4..=5]).and_then(|error_code| {
+            de_string(&bytes[6..]).map(|(error_message, bytes)| (PartitionResult { partition, error_code, error_message }, bytes))
+        })
+    })
+}
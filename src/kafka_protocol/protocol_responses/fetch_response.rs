@@ -0,0 +1,120 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 5
+#[derive(Debug)]
+pub struct FetchResponse {
+    pub throttle_time_ms: i32,
+    pub responses: Vec<Response>,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub topic: String,
+    pub partition_responses: Vec<PartitionResponse>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartitionResponse {
+    pub partition_header: PartitionHeader,
+    pub record_set: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartitionHeader {
+    pub partition: i32,
+    pub error_code: i16,
+    pub high_watermark: i64,
+    pub last_stable_offset: i64,
+    pub log_start_offset: i64,
+    pub aborted_transactions: Vec<AbortedTransaction>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AbortedTransaction {
+    pub producer_id: i64,
+    pub first_offset: i64,
+}
+
+impl ProtocolDeserializable<FetchResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<FetchResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_array(&self[4..], deserialize_response).map(|(responses, _bytes)| FetchResponse { throttle_time_ms, responses })
+        })
+    }
+}
+
+fn deserialize_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Response>> {
+    de_string(bytes).and_then(|(topic, bytes)| {
+        topic.ok_or_else(|| DeserializeError::of("Expected topic name")).and_then(|topic| {
+            de_array(bytes, deserialize_partition_response).map(|(partition_responses, bytes)| (Response { topic, partition_responses }, bytes))
+        })
+    })
+}
+
+fn deserialize_partition_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, PartitionResponse>> {
+    deserialize_partition_header(bytes).and_then(|(partition_header, bytes)| {
+        de_bytes(bytes).map(|(record_set, bytes)| (PartitionResponse { partition_header, record_set: record_set.unwrap_or_default() }, bytes))
+    })
+}
+
+fn deserialize_partition_header(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, PartitionHeader>> {
+    de_i32(&bytes[0..=3]).and_then(|partition| {
+        de_i16(&bytes[4..=5]).and_then(|error_code| {
+            de_i64(&bytes[6..=13]).and_then(|high_watermark| {
+                de_i64(&bytes[14..=21]).and_then(|last_stable_offset| {
+                    de_i64(&bytes[22..=29]).and_then(|log_start_offset| {
+                        de_array(&bytes[30..], deserialize_aborted_transaction).map(|(aborted_transactions, bytes)| {
+                            (
+                                PartitionHeader { partition, error_code, high_watermark, last_stable_offset, log_start_offset, aborted_transactions },
+                                bytes,
+                            )
+                        })
+                    })
+                })
+            })
+        })
+    })
+}
+
+fn deserialize_aborted_transaction(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, AbortedTransaction>> {
+    de_i64(&bytes[0..=7])
+        .and_then(|producer_id| de_i64(&bytes[8..=15]).map(|first_offset| (AbortedTransaction { producer_id, first_offset }, &bytes[16..])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_fetch_response() {
+        let bytes: Vec<u8> = vec![
+            0, 0, 0, 0, // throttle_time_ms
+            0, 0, 0, 1, 0, 3, 102, 111, 111, // topic
+            0, 0, 0, 2, // partition_responses
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 10, 0, 0, 0, 0, 0, 0, 0, 9, 0, 0, 0, 0, 0, 0, 0, 1, // partition 0 header
+            0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 3, // one aborted transaction
+            0, 0, 0, 2, 1, 2, // record_set
+            0, 0, 0, 1, 0, 1, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+            255, // partition 1 header
+            255, 255, 255, 255, // null aborted transactions
+            255, 255, 255, 255, // null record_set
+        ];
+        let response: FetchResponse = bytes.into_protocol_type().unwrap();
+        let partition_responses = &response.responses[0].partition_responses;
+        assert_eq!("foo", response.responses[0].topic);
+        assert_eq!(2, partition_responses.len());
+
+        let header = &partition_responses[0].partition_header;
+        assert_eq!(
+            (0, 0, 10, 9, 1),
+            (header.partition, header.error_code, header.high_watermark, header.last_stable_offset, header.log_start_offset)
+        );
+        assert_eq!((5, 3), (header.aborted_transactions[0].producer_id, header.aborted_transactions[0].first_offset));
+        assert_eq!(vec![1, 2], partition_responses[0].record_set);
+
+        let header = &partition_responses[1].partition_header;
+        assert_eq!((1, 1, -1), (header.partition, header.error_code, header.high_watermark));
+        assert!(header.aborted_transactions.is_empty());
+        assert!(partition_responses[1].record_set.is_empty());
+    }
+}
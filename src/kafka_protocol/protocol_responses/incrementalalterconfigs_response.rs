@@ -0,0 +1,36 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 0
+#[derive(Debug)]
+pub struct IncrementalAlterConfigsResponse {
+    pub throttle_time_ms: i32,
+    pub responses: Vec<Response>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub error_code: i16,
+    pub error_message: Option<String>,
+    pub resource_type: i8,
+    pub resource_name: String,
+}
+
+impl ProtocolDeserializable<IncrementalAlterConfigsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<IncrementalAlterConfigsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_array(&self[4..], deserialize_response).map(|(responses, _bytes)| IncrementalAlterConfigsResponse { throttle_time_ms, responses })
+        })
+    }
+}
+
+fn deserialize_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Response>> {
+    de_i16(&bytes[0..=1]).and_then(|error_code| {
+        de_string(&bytes[2..]).and_then(|(error_message, bytes)| {
+            let resource_type = bytes[0] as i8;
+            de_string(&bytes[1..]).and_then(|(resource_name, bytes)| match resource_name {
+                None => Err(DeserializeError::of("resource_name unexpectedly null")),
+                Some(resource_name) => Ok((Response { error_code, error_message, resource_type, resource_name }, bytes)),
+            })
+        })
+    })
+}
@@ -0,0 +1,115 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 4
+#[derive(Debug)]
+pub struct ListGroupsResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    pub groups: Vec<Group>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    pub group_id: String,
+    pub protocol_type: String,
+    /// empty when listed by a broker before Kafka 2.6
+    pub group_state: String,
+}
+
+impl ProtocolDeserializable<ListGroupsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<ListGroupsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_i16(&self[4..=5]).and_then(|error_code| {
+                de_compact_array(&self[6..], deserialize_group).map(|(groups, _bytes)| ListGroupsResponse { throttle_time_ms, error_code, groups })
+            })
+        })
+    }
+}
+
+/// Version 2, whose groups have no state
+#[derive(Debug)]
+pub struct LegacyListGroupsResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    pub groups: Vec<Group>,
+}
+
+impl ProtocolDeserializable<LegacyListGroupsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<LegacyListGroupsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_i16(&self[4..=5]).and_then(|error_code| {
+                de_array(&self[6..], deserialize_legacy_group).map(|(groups, _bytes)| LegacyListGroupsResponse {
+                    throttle_time_ms,
+                    error_code,
+                    groups,
+                })
+            })
+        })
+    }
+}
+
+fn deserialize_legacy_group(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Group>> {
+    de_string(bytes).and_then(|(group_id, bytes)| {
+        group_id.ok_or_else(|| DeserializeError::of("Expected group_id")).and_then(|group_id| {
+            de_string(bytes).map(|(protocol_type, bytes)| {
+                (Group { group_id, protocol_type: protocol_type.unwrap_or_default(), group_state: String::new() }, bytes)
+            })
+        })
+    })
+}
+
+fn deserialize_group(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Group>> {
+    de_compact_string(bytes).and_then(|(group_id, bytes)| {
+        group_id.ok_or_else(|| DeserializeError::of("Expected group_id")).and_then(|group_id| {
+            de_compact_string(bytes).and_then(|(protocol_type, bytes)| {
+                de_compact_string(bytes).and_then(|(group_state, bytes)| {
+                    de_tagged_fields(bytes).map(|(_, bytes)| {
+                        let protocol_type = protocol_type.unwrap_or_default();
+                        let group_state = group_state.unwrap_or_default();
+                        (Group { group_id, protocol_type, group_state }, bytes)
+                    })
+                })
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_listgroups_response() {
+        let bytes: Vec<u8> = vec![
+            0, 0, 0, 0, // throttle_time_ms
+            0, 0, // error_code
+            2, // one group
+            4, 102, 111, 111, // group_id
+            9, 99, 111, 110, 115, 117, 109, 101, 114, // protocol_type
+            7, 83, 116, 97, 98, 108, 101, // group_state
+            0,   // group tagged fields
+            0,   // response tagged fields
+        ];
+        let response: ListGroupsResponse = bytes.into_protocol_type().unwrap();
+        assert_eq!(
+            vec![Group { group_id: String::from("foo"), protocol_type: String::from("consumer"), group_state: String::from("Stable") }],
+            response.groups
+        );
+    }
+
+    #[test]
+    fn verify_legacy_listgroups_response() {
+        let bytes: Vec<u8> = vec![
+            0, 0, 0, 0, // throttle_time_ms
+            0, 0, // error_code
+            0, 0, 0, 1, // one group
+            0, 3, 102, 111, 111, // group_id
+            0, 8, 99, 111, 110, 115, 117, 109, 101, 114, // protocol_type
+        ];
+        let response: LegacyListGroupsResponse = bytes.into_protocol_type().unwrap();
+        assert_eq!(
+            vec![Group { group_id: String::from("foo"), protocol_type: String::from("consumer"), group_state: String::new() }],
+            response.groups
+        );
+    }
+}
@@ -29,15 +29,15 @@ impl ProtocolDeserializable<ListOffsetsResponse> for Vec<u8> {
     }
 }
 
-fn deserialize_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<Response>> {
-    de_string(&bytes).and_then(|(topic, bytes)| {
+fn deserialize_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Response>> {
+    de_string(bytes).and_then(|(topic, bytes)| {
         de_array(bytes, deserialize_partition_response).and_then(|(partition_responses, bytes)| {
             topic.map(|topic| Ok((Response { topic, partition_responses }, bytes))).unwrap_or(Err(DeserializeError::of("Topic name undefined")))
         })
     })
 }
 
-fn deserialize_partition_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<PartitionResponse>> {
+fn deserialize_partition_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, PartitionResponse>> {
     de_i32(&bytes[0..=3]).and_then(|partition| {
         de_i16(&bytes[4..=5]).and_then(|error_code| {
             de_i64(&bytes[6..=13]).and_then(|timestamp| {
@@ -0,0 +1,90 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 0
+#[derive(Debug)]
+pub struct ListPartitionReassignmentsResponse {
+    pub throttle_time_ms: i32,
+    pub error_code: i16,
+    pub error_message: Option<String>,
+    pub topics: Vec<Topic>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Topic {
+    pub topic: String,
+    pub partitions: Vec<Partition>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Partition {
+    pub partition: i32,
+    /// the current replicas, including those being added and removed
+    pub replicas: Vec<i32>,
+    pub adding_replicas: Vec<i32>,
+    pub removing_replicas: Vec<i32>,
+}
+
+impl ProtocolDeserializable<ListPartitionReassignmentsResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<ListPartitionReassignmentsResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_i16(&self[4..=5]).and_then(|error_code| {
+                de_compact_string(&self[6..]).and_then(|(error_message, bytes)| {
+                    de_compact_array(bytes, deserialize_topic).map(|(topics, _bytes)| ListPartitionReassignmentsResponse {
+                        throttle_time_ms,
+                        error_code,
+                        error_message,
+                        topics,
+                    })
+                })
+            })
+        })
+    }
+}
+
+fn deserialize_topic(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Topic>> {
+    de_compact_string(bytes).and_then(|(topic, bytes)| {
+        topic.ok_or_else(|| DeserializeError::of("Expected topic name")).and_then(|topic| {
+            de_compact_array(bytes, deserialize_partition)
+                .and_then(|(partitions, bytes)| de_tagged_fields(bytes).map(|(_, bytes)| (Topic { topic, partitions }, bytes)))
+        })
+    })
+}
+
+fn deserialize_partition(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Partition>> {
+    de_i32(&bytes[0..=3]).and_then(|partition| {
+        de_compact_array(&bytes[4..], deserialize_broker_id).and_then(|(replicas, bytes)| {
+            de_compact_array(bytes, deserialize_broker_id).and_then(|(adding_replicas, bytes)| {
+                de_compact_array(bytes, deserialize_broker_id).and_then(|(removing_replicas, bytes)| {
+                    de_tagged_fields(bytes).map(|(_, bytes)| (Partition { partition, replicas, adding_replicas, removing_replicas }, bytes))
+                })
+            })
+        })
+    })
+}
+
+fn deserialize_broker_id(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, i32>> {
+    de_i32(&bytes[0..=3]).map(|broker_id| (broker_id, &bytes[4..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_listpartitionreassignments_response() {
+        let bytes: Vec<u8> = vec![
+            0, 0, 0, 0, 0, 0, 0, // throttle_time_ms, error_code, error_message
+            2, 2, 116, 2, // topics, topic, partitions
+            0, 0, 0, 1, // partition
+            4, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, // replicas
+            2, 0, 0, 0, 3, // adding_replicas
+            2, 0, 0, 0, 1, // removing_replicas
+            0, 0, 0, // partition, topic and response tagged fields
+        ];
+        let response: ListPartitionReassignmentsResponse = bytes.into_protocol_type().unwrap();
+        assert_eq!(
+            vec![Partition { partition: 1, replicas: vec![1, 2, 3], adding_replicas: vec![3], removing_replicas: vec![1] }],
+            response.topics[0].partitions
+        );
+    }
+}
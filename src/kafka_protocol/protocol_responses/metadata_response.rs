@@ -44,7 +44,7 @@ impl ProtocolDeserializable<MetadataResponse> for Vec<u8> {
         });
 
         let result = result.and_then(|(throttle_time_ms, brokers, remaining_bytes)| {
-            de_string(&remaining_bytes).map(|(cluster_id, remaining_bytes)| (throttle_time_ms, brokers, cluster_id, remaining_bytes))
+            de_string(remaining_bytes).map(|(cluster_id, remaining_bytes)| (throttle_time_ms, brokers, cluster_id, remaining_bytes))
         });
 
         let result = result.and_then(|(throttle_time_ms, brokers, cluster_id, remaining_bytes)| {
@@ -52,7 +52,7 @@ impl ProtocolDeserializable<MetadataResponse> for Vec<u8> {
         });
 
         let result = result.and_then(|(throttle_time_ms, brokers, cluster_id, controller_id, remaining_bytes)| {
-            de_array(&remaining_bytes, deserialize_topic_metadata)
+            de_array(remaining_bytes, deserialize_topic_metadata)
                 .map(|(topic_metadata, remaining_bytes)| (throttle_time_ms, brokers, cluster_id, controller_id, topic_metadata, remaining_bytes))
         });
 
@@ -65,7 +65,7 @@ impl ProtocolDeserializable<MetadataResponse> for Vec<u8> {
     }
 }
 
-fn deserialize_broker_metadata(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<BrokerMetadata>> {
+fn deserialize_broker_metadata(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, BrokerMetadata>> {
     let result = de_i32(&bytes[0..4]).and_then(|node_id| {
         de_string(&bytes[4..]).map(|(host, remaining_bytes)| {
             let host = host.expect("Expected host string");
@@ -81,23 +81,23 @@ fn deserialize_broker_metadata(bytes: &[u8]) -> ProtocolDeserializeResult<Dynami
     result.map(|(node_id, host, port, rack, remaining_bytes)| (BrokerMetadata { node_id, host, port, rack }, remaining_bytes))
 }
 
-fn deserialize_topic_metadata(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<TopicMetadata>> {
+fn deserialize_topic_metadata(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, TopicMetadata>> {
     de_i16(&bytes[0..2]).and_then(|error_code| {
         de_string(&bytes[2..])
             .map(|(topic, remaining_bytes)| {
                 let topic = topic.expect("Unexpected empty topic name");
-                let is_internal = if remaining_bytes[0] == 1 { true } else { false };
+                let is_internal = remaining_bytes[0] == 1;
                 (topic, is_internal, &remaining_bytes[1..])
             })
             .and_then(|(topic, is_internal, remaining_bytes)| {
-                de_array(&remaining_bytes, deserialize_partition_metadata).map(|(partition_metadata, remaining_bytes)| {
+                de_array(remaining_bytes, deserialize_partition_metadata).map(|(partition_metadata, remaining_bytes)| {
                     (TopicMetadata { error_code, topic, is_internal, partition_metadata }, remaining_bytes)
                 })
             })
     })
 }
 
-fn deserialize_partition_metadata(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<PartitionMetadata>> {
+fn deserialize_partition_metadata(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, PartitionMetadata>> {
     de_i16(&bytes[0..2]).and_then(|error_code| {
         de_i32(&bytes[2..6]).and_then(|partition| {
             de_i32(&bytes[6..10]).and_then(|leader| {
@@ -1,8 +1,26 @@
 pub mod alterconfigs_response;
+pub mod alterpartitionreassignments_response;
+pub mod createacls_response;
+pub mod createpartitions_response;
 pub mod createtopics_response;
+pub mod deleteacls_response;
+pub mod deletegroups_response;
+pub mod deleterecords_response;
 pub mod deletetopics_response;
+pub mod describeacls_response;
 pub mod describeconfigs_response;
+pub mod describegroups_response;
+pub mod describelogdirs_response;
+pub mod electleaders_response;
+pub mod fetch_response;
 pub mod findcoordinator_response;
+pub mod incrementalalterconfigs_response;
+pub mod listgroups_response;
 pub mod listoffsets_response;
+pub mod listpartitionreassignments_response;
 pub mod metadata_response;
+pub mod offsetcommit_response;
 pub mod offsetfetch_response;
+pub mod produce_response;
+pub mod saslauthenticate_response;
+pub mod saslhandshake_response;
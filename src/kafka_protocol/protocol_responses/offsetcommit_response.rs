@@ -0,0 +1,40 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 5
+#[derive(Debug)]
+pub struct OffsetCommitResponse {
+    pub throttle_time_ms: i32,
+    pub responses: Vec<Response>,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub topic: String,
+    pub partition_responses: Vec<PartitionResponse>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartitionResponse {
+    pub partition: i32,
+    pub error_code: i16,
+}
+
+impl ProtocolDeserializable<OffsetCommitResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<OffsetCommitResponse> {
+        de_i32(&self[0..=3]).and_then(|throttle_time_ms| {
+            de_array(&self[4..], deserialize_response).map(|(responses, _bytes)| OffsetCommitResponse { throttle_time_ms, responses })
+        })
+    }
+}
+
+fn deserialize_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Response>> {
+    de_string(bytes).and_then(|(topic, bytes)| {
+        topic.ok_or_else(|| DeserializeError::of("Expected topic name")).and_then(|topic| {
+            de_array(bytes, deserialize_partition_response).map(|(partition_responses, bytes)| (Response { topic, partition_responses }, bytes))
+        })
+    })
+}
+
+fn deserialize_partition_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, PartitionResponse>> {
+    de_i32(&bytes[0..=3]).and_then(|partition| de_i16(&bytes[4..=5]).map(|error_code| (PartitionResponse { partition, error_code }, &bytes[6..])))
+}
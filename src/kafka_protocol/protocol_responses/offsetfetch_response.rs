@@ -32,15 +32,15 @@ impl ProtocolDeserializable<OffsetFetchResponse> for Vec<u8> {
     }
 }
 
-fn deserialize_responses(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<Response>> {
-    de_string(&bytes).and_then(|(topic, bytes)| {
+fn deserialize_responses(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Response>> {
+    de_string(bytes).and_then(|(topic, bytes)| {
         topic.ok_or_else(move || DeserializeError::of("Expected topic name")).and_then(|topic| {
             de_array(bytes, deserialize_partition_responses).map(|(partition_responses, bytes)| (Response { topic, partition_responses }, bytes))
         })
     })
 }
 
-fn deserialize_partition_responses(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<PartitionResponse>> {
+fn deserialize_partition_responses(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, PartitionResponse>> {
     de_i32(&bytes[0..=3]).and_then(|partition| {
         de_i64(&bytes[4..=11]).and_then(|offset| {
             de_string(&bytes[12..]).and_then(|(metadata, bytes)| {
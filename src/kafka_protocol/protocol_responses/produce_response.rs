@@ -0,0 +1,73 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 3
+#[derive(Debug)]
+pub struct ProduceResponse {
+    pub responses: Vec<Response>,
+    pub throttle_time_ms: i32,
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub topic: String,
+    pub partition_responses: Vec<PartitionResponse>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartitionResponse {
+    pub partition: i32,
+    pub error_code: i16,
+    pub base_offset: i64,
+    pub log_append_time: i64,
+}
+
+impl ProtocolDeserializable<ProduceResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<ProduceResponse> {
+        de_array(&self, deserialize_response)
+            .and_then(|(responses, bytes)| de_i32(&bytes[0..=3]).map(|throttle_time_ms| ProduceResponse { responses, throttle_time_ms }))
+    }
+}
+
+fn deserialize_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Response>> {
+    de_string(bytes).and_then(|(topic, bytes)| {
+        topic.ok_or_else(|| DeserializeError::of("Expected topic name")).and_then(|topic| {
+            de_array(bytes, deserialize_partition_response).map(|(partition_responses, bytes)| (Response { topic, partition_responses }, bytes))
+        })
+    })
+}
+
+fn deserialize_partition_response(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, PartitionResponse>> {
+    de_i32(&bytes[0..=3]).and_then(|partition| {
+        de_i16(&bytes[4..=5]).and_then(|error_code| {
+            de_i64(&bytes[6..=13]).and_then(|base_offset| {
+                de_i64(&bytes[14..=21])
+                    .map(|log_append_time| (PartitionResponse { partition, error_code, base_offset, log_append_time }, &bytes[22..]))
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_produce_response() {
+        let bytes: Vec<u8> = vec![
+            0, 0, 0, 1, 0, 3, 102, 111, 111, // topic
+            0, 0, 0, 1, 0, 0, 0, 2, // partition
+            0, 0, // error_code
+            0, 0, 0, 0, 0, 0, 0, 42, // base_offset
+            255, 255, 255, 255, 255, 255, 255, 255, // log_append_time
+            0, 0, 0, 7, // throttle_time_ms
+        ];
+        let response: ProduceResponse = bytes.into_protocol_type().unwrap();
+        assert_eq!(7, response.throttle_time_ms);
+        assert_eq!("foo", response.responses[0].topic);
+        let partition_response = &response.responses[0].partition_responses[0];
+        assert_eq!(
+            (2, 0, 42, -1),
+            (partition_response.partition, partition_response.error_code, partition_response.base_offset, partition_response.log_append_time)
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+#[derive(Debug)]
+pub struct SaslAuthenticateResponse {
+    pub error_code: i16,
+    pub error_message: Option<String>,
+    pub auth_bytes: Vec<u8>,
+    /// how long the authenticated session lasts, or 0 if it does not expire
+    pub session_lifetime_ms: i64,
+}
+
+impl ProtocolDeserializable<SaslAuthenticateResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<SaslAuthenticateResponse> {
+        de_i16(&self[0..=1]).and_then(|error_code| {
+            de_string(&self[2..]).and_then(|(error_message, bytes)| {
+                de_bytes(bytes).and_then(|(auth_bytes, bytes)| {
+                    bytes.get(0..8).ok_or_else(|| DeserializeError::of("Expected session_lifetime_ms")).and_then(de_i64).map(|session_lifetime_ms| {
+                        SaslAuthenticateResponse { error_code, error_message, auth_bytes: auth_bytes.unwrap_or_default(), session_lifetime_ms }
+                    })
+                })
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_saslauthenticate_response() {
+        let bytes: Vec<u8> = vec![0, 58, 0, 3, 98, 97, 100, 0, 0, 0, 1, 7, 0, 0, 0, 0, 0, 0, 0, 9];
+        let response: SaslAuthenticateResponse = bytes.into_protocol_type().unwrap();
+        assert_eq!(58, response.error_code);
+        assert_eq!(Some(String::from("bad")), response.error_message);
+        assert_eq!(vec![7], response.auth_bytes);
+        assert_eq!(9, response.session_lifetime_ms);
+    }
+}
@@ -0,0 +1,35 @@
+use crate::kafka_protocol::protocol_serializable::*;
+
+/// Version 1
+#[derive(Debug)]
+pub struct SaslHandshakeResponse {
+    pub error_code: i16,
+    /// the mechanisms enabled on the broker
+    pub mechanisms: Vec<String>,
+}
+
+impl ProtocolDeserializable<SaslHandshakeResponse> for Vec<u8> {
+    fn into_protocol_type(self) -> ProtocolDeserializeResult<SaslHandshakeResponse> {
+        de_i16(&self[0..=1]).and_then(|error_code| {
+            de_array(&self[2..], deserialize_mechanism).map(|(mechanisms, _bytes)| SaslHandshakeResponse { error_code, mechanisms })
+        })
+    }
+}
+
+fn deserialize_mechanism(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, String>> {
+    de_string(bytes)
+        .and_then(|(mechanism, bytes)| mechanism.ok_or_else(|| DeserializeError::of("Expected mechanism")).map(|mechanism| (mechanism, bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_saslhandshake_response() {
+        let bytes: Vec<u8> = vec![0, 33, 0, 0, 0, 2, 0, 5, 80, 76, 65, 73, 78, 0, 2, 71, 83];
+        let response: SaslHandshakeResponse = bytes.into_protocol_type().unwrap();
+        assert_eq!(33, response.error_code);
+        assert_eq!(vec![String::from("PLAIN"), String::from("GS")], response.mechanisms);
+    }
+}
@@ -1,6 +1,5 @@
 extern crate byteorder;
 
-use std::error::Error;
 use std::io::Cursor;
 use std::io::Result as IOResult;
 use std::str::from_utf8;
@@ -40,7 +39,7 @@ impl DeserializeError {
 
 // Deserializer Functions
 fn deserialize_number<N>(bytes: &[u8], f: fn(Cursor<Vec<u8>>) -> IOResult<N>) -> ProtocolDeserializeResult<N> {
-    f(Cursor::new(bytes.to_vec())).map_err(|e| DeserializeError::of(e.description()))
+    f(Cursor::new(bytes.to_vec())).map_err(|e| DeserializeError::of(&e.to_string()))
 }
 
 pub fn de_i32(bytes: &[u8]) -> ProtocolDeserializeResult<i32> {
@@ -57,7 +56,7 @@ pub fn de_i64(bytes: &[u8]) -> ProtocolDeserializeResult<i64> {
 
 pub type DynamicSize<'a, T> = (T, &'a [u8]); // &[u8] == remaining bytes after
 
-pub fn de_array<T, F>(bytes: &[u8], deserialize_t: F) -> ProtocolDeserializeResult<DynamicSize<Vec<T>>>
+pub fn de_array<T, F>(bytes: &[u8], deserialize_t: F) -> ProtocolDeserializeResult<DynamicSize<'_, Vec<T>>>
 where
     F: Fn(&[u8]) -> ProtocolDeserializeResult<DynamicSize<T>>,
 {
@@ -68,7 +67,7 @@ where
     })
 }
 
-fn de_array_transform<T, F>(bytes: &[u8], elements: i32, deserialize_t: F) -> ProtocolDeserializeResult<DynamicSize<Vec<T>>>
+fn de_array_transform<T, F>(bytes: &[u8], elements: i32, deserialize_t: F) -> ProtocolDeserializeResult<DynamicSize<'_, Vec<T>>>
 where
     F: Fn(&[u8]) -> ProtocolDeserializeResult<DynamicSize<T>>,
 {
@@ -86,7 +85,7 @@ where
     }
 }
 
-pub fn de_string(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<Option<String>>> {
+pub fn de_string(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Option<String>>> {
     de_i16(&bytes[0..2]).and_then(|byte_length| match byte_length {
         -1 => Ok((None, &bytes[2..])),
         _ => {
@@ -96,15 +95,94 @@ pub fn de_string(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<Option<S
 
             match from_utf8(string_bytes) {
                 Ok(string) => Ok((Some(String::from(string)), remaining_bytes)),
-                _ => Err(DeserializeError::of(&format!("Failed to deserialize string {:?}", to_hex_array(&string_bytes.to_vec())))),
+                _ => Err(DeserializeError::of(&format!("Failed to deserialize string {:?}", to_hex_array(string_bytes)))),
             }
         }
     })
 }
 
+pub fn de_bytes(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Option<Vec<u8>>>> {
+    bytes.get(0..4).ok_or_else(|| DeserializeError::of("Unexpected end of bytes reading bytes length")).and_then(de_i32).and_then(|byte_length| {
+        match byte_length {
+            -1 => Ok((None, &bytes[4..])),
+            _ if byte_length >= 0 && (byte_length as usize) <= bytes.len() - 4 => {
+                let end_index = (byte_length as usize) + 4;
+                Ok((Some(bytes[4..end_index].to_vec()), &bytes[end_index..]))
+            }
+            _ => Err(DeserializeError::of(&format!("Invalid bytes length {} with {} bytes remaining", byte_length, bytes.len() - 4))),
+        }
+    })
+}
+
+pub fn de_varint(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, i32>> {
+    de_leb128(bytes, 5).map(|(zigzag, bytes)| ((((zigzag as u32) >> 1) as i32) ^ -((zigzag & 1) as i32), bytes))
+}
+
+pub fn de_varlong(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, i64>> {
+    de_leb128(bytes, 10).map(|(zigzag, bytes)| (((zigzag >> 1) as i64) ^ -((zigzag & 1) as i64), bytes))
+}
+
+pub fn de_unsigned_varint(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, u32>> {
+    de_leb128(bytes, 5).map(|(unsigned, bytes)| (unsigned as u32, bytes))
+}
+
+fn de_leb128(bytes: &[u8], max_bytes: usize) -> ProtocolDeserializeResult<DynamicSize<'_, u64>> {
+    let mut value: u64 = 0;
+    for (index, byte) in bytes.iter().enumerate().take(max_bytes) {
+        value |= u64::from(byte & 0x7F) << (7 * index);
+        if byte & 0x80 == 0 {
+            return Ok((value, &bytes[index + 1..]));
+        }
+    }
+    if bytes.len() < max_bytes {
+        Err(DeserializeError::of("Unexpected end of bytes reading varint"))
+    } else {
+        Err(DeserializeError::of(&format!("Varint exceeds {} bytes", max_bytes)))
+    }
+}
+
+pub fn de_compact_string(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, Option<String>>> {
+    de_unsigned_varint(bytes).and_then(|(byte_length_plus_one, bytes)| match byte_length_plus_one {
+        0 => Ok((None, bytes)),
+        _ if (byte_length_plus_one - 1) as usize <= bytes.len() => {
+            let (string_bytes, remaining_bytes) = bytes.split_at((byte_length_plus_one - 1) as usize);
+            match from_utf8(string_bytes) {
+                Ok(string) => Ok((Some(String::from(string)), remaining_bytes)),
+                _ => Err(DeserializeError::of(&format!("Failed to deserialize compact string {:?}", to_hex_array(string_bytes)))),
+            }
+        }
+        _ => Err(DeserializeError::of(&format!("Invalid compact string length {} with {} bytes remaining", byte_length_plus_one - 1, bytes.len()))),
+    })
+}
+
+/// A null compact array is deserialized as empty
+pub fn de_compact_array<T, F>(bytes: &[u8], deserialize_t: F) -> ProtocolDeserializeResult<DynamicSize<'_, Vec<T>>>
+where
+    F: Fn(&[u8]) -> ProtocolDeserializeResult<DynamicSize<T>>,
+{
+    de_unsigned_varint(bytes).and_then(|(elements_plus_one, bytes)| de_array_transform(bytes, elements_plus_one as i32 - 1, deserialize_t))
+}
+
+/// Tagged fields are skipped as none are currently understood
+pub fn de_tagged_fields(bytes: &[u8]) -> ProtocolDeserializeResult<DynamicSize<'_, ()>> {
+    de_unsigned_varint(bytes).and_then(|(fields, bytes)| {
+        (0..fields)
+            .try_fold(bytes, |bytes, _| {
+                de_unsigned_varint(bytes).and_then(|(_tag, bytes)| {
+                    de_unsigned_varint(bytes).and_then(|(size, bytes)| {
+                        bytes.get(size as usize..).ok_or_else(|| DeserializeError::of(&format!("Invalid tagged field size {}", size)))
+                    })
+                })
+            })
+            .map(|bytes| ((), bytes))
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives;
     use crate::kafka_protocol::protocol_primitives::ProtocolPrimitives::*;
+    use crate::kafka_protocol::protocol_primitives::{CompactArray, CompactString};
 
     use super::*;
 
@@ -133,6 +211,84 @@ mod tests {
         }
     }
 
+    proptest! {
+        #[test]
+        fn verify_de_bytes(ref b in proptest::collection::vec(proptest::prelude::any::<u8>(), 0..256)) {
+
+            let bytes = Bytes(b.clone()).into_protocol_bytes().unwrap();
+            match de_bytes(&bytes) {
+                Ok((Some(deserialized), remaining_bytes)) => {
+                    assert!(remaining_bytes.is_empty());
+                    assert_eq!(b.clone(), deserialized);
+                },
+                _ => panic!("test failed")
+            }
+
+            // verify null bytes
+            let bytes = ProtocolPrimitives::null_bytes().into_protocol_bytes().unwrap();
+            match de_bytes(&bytes) {
+                Ok((None, remaining_bytes)) => assert!(remaining_bytes.is_empty()),
+                _ => panic!("test failed")
+            }
+        }
+    }
+
+    #[test]
+    fn verify_de_bytes_with_invalid_length() {
+        assert!(de_bytes(&[0, 0, 0]).is_err());
+        assert!(de_bytes(&[0, 0, 0, 2, 1]).is_err());
+        assert!(de_bytes(&[255, 255, 255, 254, 1]).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn verify_de_varint(i in proptest::prelude::any::<i32>(), l in proptest::prelude::any::<i64>()) {
+
+            let mut bytes = VarInt(i).into_protocol_bytes().unwrap();
+            bytes.append(&mut vec![42]);
+            match de_varint(&bytes) {
+                Ok((varint, remaining_bytes)) => {
+                    assert_eq!(i, varint);
+                    assert_eq!(remaining_bytes.to_vec(), vec![42]);
+                },
+                _ => panic!("test failed")
+            }
+
+            let bytes = VarLong(l).into_protocol_bytes().unwrap();
+            match de_varlong(&bytes) {
+                Ok((varlong, remaining_bytes)) => {
+                    assert_eq!(l, varlong);
+                    assert!(remaining_bytes.is_empty());
+                },
+                _ => panic!("test failed")
+            }
+
+            // verify truncated varints
+            assert!(de_varlong(&bytes[0..bytes.len() - 1]).is_err());
+            assert!(de_varint(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]).is_err());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn verify_de_compact_array(ref a in ".*", ref b in ".*") {
+
+            let array = CompactArray(vec![CompactString(a.clone()), CompactString(b.clone())]);
+            let bytes = [array.into_protocol_bytes().unwrap(), vec![1, 0, 2, 42, 42, 7]].concat();
+            let result = de_compact_array(&bytes, |element| {
+                de_compact_string(element).map(|(opt_string, remaining_bytes)| (opt_string.expect("should be deserializable string"), remaining_bytes))
+            });
+
+            match result {
+                Ok((strings, remaining_bytes)) => {
+                    assert_eq!(vec![a.clone(), b.clone()], strings);
+                    assert_eq!(vec![7], de_tagged_fields(remaining_bytes).unwrap().1.to_vec());
+                },
+                _ => panic!("test failed")
+            }
+        }
+    }
+
     proptest! {
         #[test]
         fn verify_de_array(ref a in ".*", ref b in ".*", ref c in ".*") {
@@ -1,22 +1,31 @@
 extern crate byteorder;
+#[cfg(feature = "native-tls")]
 extern crate native_tls;
 #[cfg(test)]
 #[macro_use]
 extern crate proptest;
 
+pub mod admin;
 pub mod api_client;
 pub mod kafka_protocol;
 
+use crate::api_client::sasl::SaslMechanism;
+use crate::api_client::tls::TlsConfig;
+
 #[derive(Clone, Debug)]
 pub struct KafkaServerAddr {
     pub domain: String,
     pub port: i32,
     pub use_tls: bool,
+    /// how TLS connections are established, when `use_tls` is set
+    pub tls_config: TlsConfig,
+    /// when set, each connection is authenticated with SASL (over plaintext or TLS)
+    pub sasl: Option<SaslMechanism>,
 }
 
 impl KafkaServerAddr {
     pub fn of(domain: String, port: i32, use_tls: bool) -> KafkaServerAddr {
-        KafkaServerAddr { domain, port, use_tls }
+        KafkaServerAddr { domain, port, use_tls, tls_config: TlsConfig::default(), sasl: None }
     }
     pub fn from_arg(addr_arg: &str, use_tls: bool) -> Option<KafkaServerAddr> {
         let split: Vec<&str> = addr_arg.split(':').collect::<Vec<&str>>();
@@ -25,23 +34,34 @@ impl KafkaServerAddr {
             _ => None,
         }
     }
+    /// Connects with TLS, configured by `tls_config`
+    pub fn with_tls(self, tls_config: TlsConfig) -> KafkaServerAddr {
+        KafkaServerAddr { use_tls: true, tls_config, ..self }
+    }
+    pub fn with_sasl(self, mechanism: SaslMechanism) -> KafkaServerAddr {
+        KafkaServerAddr { sasl: Some(mechanism), ..self }
+    }
     pub fn as_socket_addr(&self) -> String {
         format!("{}:{}", self.domain, self.port)
     }
+    /// Another broker (e.g. from a MetadataResponse), connected to the same way as this one
+    pub fn with_host(&self, domain: &str, port: i32) -> KafkaServerAddr {
+        KafkaServerAddr { domain: String::from(domain), port, ..self.clone() }
+    }
 }
 
-fn to_hex_array(bytes: &Vec<u8>) -> Vec<String> {
+fn to_hex_array(bytes: &[u8]) -> Vec<String> {
     bytes.iter().cloned().map(|b| format!("0x{:02X}", b)).collect::<Vec<String>>()
 }
 
-type Thunk<A, E> = Box<Fn() -> Result<A, E>>;
+type Thunk<A, E> = Box<dyn Fn() -> Result<A, E>>;
 
 pub struct IO<A, E> {
     thunk: Thunk<A, E>,
 }
 
 impl<A: 'static, E: 'static> IO<A, E> {
-    pub fn new(f: Box<Fn() -> Result<A, E>>) -> IO<A, E> {
+    pub fn new(f: Box<dyn Fn() -> Result<A, E>>) -> IO<A, E> {
         IO { thunk: f }
     }
 
@@ -50,7 +70,7 @@ impl<A: 'static, E: 'static> IO<A, E> {
     }
 
     #[inline]
-    pub fn map<B: 'static>(self, f: Box<Fn(A) -> B>) -> IO<B, E> {
+    pub fn map<B: 'static>(self, f: Box<dyn Fn(A) -> B>) -> IO<B, E> {
         IO {
             thunk: Box::new(move || {
                 let run: Result<A, E> = (self.thunk)();
@@ -63,7 +83,7 @@ impl<A: 'static, E: 'static> IO<A, E> {
     }
 
     #[inline]
-    pub fn and_then<B: 'static>(self, f: Box<Fn(A) -> IO<B, E>>) -> IO<B, E> {
+    pub fn and_then<B: 'static>(self, f: Box<dyn Fn(A) -> IO<B, E>>) -> IO<B, E> {
         IO {
             thunk: Box::new(move || {
                 let run: Result<A, E> = (self.thunk)();
@@ -79,7 +99,7 @@ impl<A: 'static, E: 'static> IO<A, E> {
     }
 
     #[inline]
-    pub fn and_then_result<B: 'static>(self, f: Box<Fn(A) -> Result<B, E>>) -> IO<B, E> {
+    pub fn and_then_result<B: 'static>(self, f: Box<dyn Fn(A) -> Result<B, E>>) -> IO<B, E> {
         IO {
             thunk: Box::new(move || {
                 let run: Result<A, E> = (self.thunk)();